@@ -6,6 +6,8 @@ use std::{env, fs, fs::File, path::Path};
 /// To generate updated proto objects:
 /// 1. Place `vss.proto` file in `src/proto/`
 /// 2. run `RUSTFLAGS="--cfg genproto" cargo build`
+///
+/// Unlike `vss.proto`, `envelope.proto` is owned by the client and kept in `src/proto/`.
 fn main() {
 	#[cfg(genproto)]
 	generate_protos();
@@ -18,9 +20,59 @@ fn generate_protos() {
 				"src/proto/vss.proto",
 		).unwrap();
 
-	prost_build::compile_protos(&["src/proto/vss.proto"], &["src/"]).unwrap();
-	let from_path = Path::new(&env::var("OUT_DIR").unwrap()).join("vss.rs");
-	fs::copy(from_path, "src/types.rs").unwrap();
+	let mut config = prost_build::Config::new();
+	// Support the JSON transport mode, see `json::TransportMode`.
+	for message in [
+		"GetObjectRequest",
+		"GetObjectResponse",
+		"PutObjectRequest",
+		"PutObjectResponse",
+		"DeleteObjectRequest",
+		"DeleteObjectResponse",
+		"ListKeyVersionsRequest",
+		"ListKeyVersionsResponse",
+		"ErrorResponse",
+		"KeyValue",
+	] {
+		let path = format!(".vss.{}", message);
+		config.type_attribute(
+			&path,
+			"#[cfg_attr(feature = \"json\", derive(serde::Serialize, serde::Deserialize))]",
+		);
+		config.type_attribute(
+			&path,
+			"#[cfg_attr(feature = \"json\", serde(rename_all = \"camelCase\", default))]",
+		);
+	}
+	let skip_none = "skip_serializing_if = \"Option::is_none\"";
+	for (field, attribute) in [
+		(
+			"PutObjectRequest.global_version",
+			format!("with = \"crate::json::optional_int64\", {}", skip_none),
+		),
+		("DeleteObjectRequest.key_value", skip_none.to_string()),
+		("GetObjectResponse.value", skip_none.to_string()),
+		("ListKeyVersionsRequest.key_prefix", skip_none.to_string()),
+		("ListKeyVersionsRequest.page_size", skip_none.to_string()),
+		("ListKeyVersionsRequest.page_token", skip_none.to_string()),
+		("ListKeyVersionsResponse.next_page_token", skip_none.to_string()),
+		(
+			"ListKeyVersionsResponse.global_version",
+			format!("with = \"crate::json::optional_int64\", {}", skip_none),
+		),
+		("ErrorResponse.error_code", "with = \"crate::json::error_code\"".to_string()),
+		("KeyValue.version", "with = \"crate::json::int64\"".to_string()),
+		("KeyValue.value", "with = \"crate::json::bytes\"".to_string()),
+	] {
+		config.field_attribute(
+			format!(".vss.{}", field),
+			format!("#[cfg_attr(feature = \"json\", serde({}))]", attribute),
+		);
+	}
+	config.compile_protos(&["src/proto/vss.proto", "src/proto/envelope.proto"], &["src/"]).unwrap();
+	let out_dir = env::var("OUT_DIR").unwrap();
+	fs::copy(Path::new(&out_dir).join("vss.rs"), "src/types.rs").unwrap();
+	fs::copy(Path::new(&out_dir).join("envelope.rs"), "src/envelope.rs").unwrap();
 }
 
 #[cfg(genproto)]
@@ -0,0 +1,90 @@
+use async_trait::async_trait;
+use bitcoin_hashes::{sha256, Hash, HashEngine, Hmac, HmacEngine};
+
+use crate::error::VssError;
+
+/// Provides bearer tokens, e.g. JWTs, authenticating requests to the VSS server.
+///
+/// The token is sent as `Authorization: Bearer <token>` header of each request. Please refer to
+/// [`VssClientBuilder::auth_provider`] for configuring a [`VssClient`] to use it.
+///
+/// [`VssClient`]: crate::client::VssClient
+/// [`VssClientBuilder::auth_provider`]: crate::client::VssClientBuilder::auth_provider
+#[async_trait]
+pub trait AuthProvider: Send + Sync {
+	/// Returns the bearer token to be used for a VSS request.
+	///
+	/// This method is called on each request, and should likely perform some form of caching.
+	async fn token(&self) -> Result<String, VssError>;
+
+	/// Returns a freshly obtained bearer token, as the VSS server rejected a request authenticated
+	/// with the current one as unauthorized.
+	///
+	/// The rejected request is retried exactly once with the returned token, without counting
+	/// towards the attempts of the configured retry policy.
+	async fn refresh(&self) -> Result<String, VssError>;
+}
+
+/// Signs requests to the VSS server, as an alternative to bearer tokens for deployments
+/// authenticating requests by their signature.
+///
+/// The signature is sent as `X-VSS-Signature` header of each request, along with the `timestamp`
+/// it was computed for as `X-VSS-Timestamp` header. Please refer to
+/// [`VssClientBuilder::request_signer`] for configuring a [`VssClient`] to use it.
+///
+/// [`VssClient`]: crate::client::VssClient
+/// [`VssClientBuilder::request_signer`]: crate::client::VssClientBuilder::request_signer
+pub trait RequestSigner: Send + Sync {
+	/// Returns the signature of a request with the given HTTP `method`, `url` and `body`, sent at
+	/// `timestamp`, given in seconds since the UNIX epoch.
+	fn sign(&self, method: &str, url: &str, body: &[u8], timestamp: u64) -> String;
+}
+
+/// A [`RequestSigner`] signing requests using HMAC-SHA256 under a shared `secret_key`.
+///
+/// The signature is the hex-encoded HMAC over the concatenation of the `method`, the `url`, the
+/// hex-encoded SHA256 hash of the `body` and the decimal `timestamp`. As the timestamp is part of
+/// the signature, the server is expected to only accept signatures within a tolerance window
+/// around its own clock.
+pub struct HmacSigner {
+	secret_key: Vec<u8>,
+}
+
+impl HmacSigner {
+	/// Constructs a new instance signing requests under the given `secret_key`.
+	pub fn new(secret_key: Vec<u8>) -> Self {
+		Self { secret_key }
+	}
+}
+
+impl RequestSigner for HmacSigner {
+	fn sign(&self, method: &str, url: &str, body: &[u8], timestamp: u64) -> String {
+		let mut engine = HmacEngine::<sha256::Hash>::new(&self.secret_key);
+		engine.input(method.as_bytes());
+		engine.input(url.as_bytes());
+		engine.input(sha256::Hash::hash(body).to_string().as_bytes());
+		engine.input(timestamp.to_string().as_bytes());
+		Hmac::from_engine(engine).to_string()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn hmac_signer_signs_requests() {
+		let signer = HmacSigner::new(b"secret".to_vec());
+		let url = "https://vss.example.com/vss/getObject";
+		let signature = signer.sign("POST", url, b"body", 1700000000);
+		assert_eq!(signature, "943aad20baf030cb5602f71a736336ed8bed1292455d9201b647db735eef9289");
+
+		// Any change of the signed request results in a different signature.
+		assert_ne!(signer.sign("POST", url, b"other", 1700000000), signature);
+		assert_ne!(signer.sign("POST", url, b"body", 1700000001), signature);
+		assert_ne!(
+			HmacSigner::new(b"other".to_vec()).sign("POST", url, b"body", 1700000000),
+			signature
+		);
+	}
+}
@@ -0,0 +1,233 @@
+use prost::Message;
+use reqwest::blocking::Client;
+use reqwest::header::{HeaderMap, CONTENT_TYPE};
+use std::collections::HashMap;
+use std::fmt;
+use tokio::runtime::{Builder, Runtime};
+
+use crate::client::{
+	check_get_object_response, decode_response, VssClient, APPLICATION_OCTET_STREAM,
+	DEFAULT_USER_AGENT,
+};
+use crate::error::VssError;
+use crate::headers::get_headermap;
+use crate::types::{
+	DeleteObjectRequest, DeleteObjectResponse, GetObjectRequest, GetObjectResponse,
+	ListKeyVersionsRequest, ListKeyVersionsResponse, PutObjectRequest, PutObjectResponse,
+};
+use crate::util::retry::{retry_blocking, RetryPolicy};
+
+// Like `VssClient`'s default HTTP client, panics if the TLS backend can't be initialized.
+fn default_http_client() -> Client {
+	Client::builder().user_agent(DEFAULT_USER_AGENT).build().expect("Failed to build HTTP client")
+}
+
+/// Blocking thin-client to access a hosted instance of Versioned Storage Service (VSS).
+///
+/// Provides the same API as [`VssClient`], for use without an async runtime. Retries are performed
+/// according to the retry policy, blocking the current thread in between attempts.
+///
+/// **Caution**: Like [`reqwest::blocking::Client`], this must not be used from within an async
+/// runtime.
+///
+/// For blocking access to a [`VssClient`] with all of its configuration, e.g. an [`AuthProvider`],
+/// refer to [`VssClientBlockingWrapper`].
+///
+/// [`AuthProvider`]: crate::auth::AuthProvider
+#[derive(Clone)]
+pub struct VssBlockingClient<R>
+where
+	R: RetryPolicy<E = VssError>,
+{
+	base_url: String,
+	client: Client,
+	retry_policy: R,
+	headers: HashMap<String, String>,
+}
+
+impl<R: RetryPolicy<E = VssError>> VssBlockingClient<R> {
+	/// Constructs a [`VssBlockingClient`] using `base_url` as the VSS server endpoint.
+	pub fn new(base_url: String, retry_policy: R) -> Self {
+		Self::from_client(base_url, default_http_client(), retry_policy)
+	}
+
+	/// Constructs a [`VssBlockingClient`] from a given [`reqwest::blocking::Client`], using
+	/// `base_url` as the VSS server endpoint.
+	pub fn from_client(base_url: String, client: Client, retry_policy: R) -> Self {
+		Self { base_url, client, retry_policy, headers: HashMap::new() }
+	}
+
+	/// Constructs a [`VssBlockingClient`] using `base_url` as the VSS server endpoint.
+	///
+	/// The given `headers` are sent with every request.
+	pub fn new_with_headers(
+		base_url: String, retry_policy: R, headers: HashMap<String, String>,
+	) -> Self {
+		Self { base_url, client: default_http_client(), retry_policy, headers }
+	}
+
+	/// Returns the underlying base URL.
+	pub fn base_url(&self) -> &str {
+		&self.base_url
+	}
+
+	/// Fetches a value against a given `key` in `request`.
+	/// Makes a service call to the `GetObject` endpoint of the VSS server.
+	/// For API contract/usage, refer to docs for [`GetObjectRequest`] and [`GetObjectResponse`].
+	pub fn get_object(&self, request: &GetObjectRequest) -> Result<GetObjectResponse, VssError> {
+		retry_blocking(
+			|| {
+				let url = format!("{}/getObject", self.base_url);
+				self.post_request(request, &url).and_then(check_get_object_response)
+			},
+			&self.retry_policy,
+		)
+	}
+
+	/// Writes multiple [`PutObjectRequest::transaction_items`] as part of a single transaction.
+	/// Makes a service call to the `PutObject` endpoint of the VSS server, with multiple items.
+	/// Items in the `request` are written in a single all-or-nothing transaction.
+	/// For API contract/usage, refer to docs for [`PutObjectRequest`] and [`PutObjectResponse`].
+	pub fn put_object(&self, request: &PutObjectRequest) -> Result<PutObjectResponse, VssError> {
+		retry_blocking(
+			|| {
+				let url = format!("{}/putObjects", self.base_url);
+				self.post_request(request, &url)
+			},
+			&self.retry_policy,
+		)
+	}
+
+	/// Deletes the given `key` and `value` in `request`.
+	/// Makes a service call to the `DeleteObject` endpoint of the VSS server.
+	/// For API contract/usage, refer to docs for [`DeleteObjectRequest`] and [`DeleteObjectResponse`].
+	pub fn delete_object(
+		&self, request: &DeleteObjectRequest,
+	) -> Result<DeleteObjectResponse, VssError> {
+		retry_blocking(
+			|| {
+				let url = format!("{}/deleteObject", self.base_url);
+				self.post_request(request, &url)
+			},
+			&self.retry_policy,
+		)
+	}
+
+	/// Lists keys and their corresponding version for a given [`ListKeyVersionsRequest::store_id`].
+	/// Makes a service call to the `ListKeyVersions` endpoint of the VSS server.
+	/// For API contract/usage, refer to docs for [`ListKeyVersionsRequest`] and [`ListKeyVersionsResponse`].
+	pub fn list_key_versions(
+		&self, request: &ListKeyVersionsRequest,
+	) -> Result<ListKeyVersionsResponse, VssError> {
+		retry_blocking(
+			|| {
+				let url = format!("{}/listKeyVersions", self.base_url);
+				self.post_request(request, &url)
+			},
+			&self.retry_policy,
+		)
+	}
+
+	fn post_request<Rq: Message, Rs: Message + Default>(
+		&self, request: &Rq, url: &str,
+	) -> Result<Rs, VssError> {
+		let request_body = request.encode_to_vec();
+		let headermap =
+			get_headermap(&self.headers).map_err(|e| VssError::AuthError(e.to_string()))?;
+		let response_raw = self
+			.client
+			.post(url)
+			.header(CONTENT_TYPE, APPLICATION_OCTET_STREAM)
+			.headers(headermap)
+			.body(request_body)
+			.send()?;
+		let status = response_raw.status();
+		let headers =
+			if status.is_success() { HeaderMap::new() } else { response_raw.headers().clone() };
+		let payload = response_raw.bytes()?;
+		decode_response(status, &headers, payload)
+	}
+}
+
+/// A blocking wrapper around a [`VssClient`], running its requests on a runtime owned by the
+/// wrapper.
+///
+/// Unlike [`VssBlockingClient`], this supports all configuration of [`VssClient`], e.g. as built
+/// via [`VssClientBuilder`], at the cost of a single-threaded Tokio runtime per instance. Hence, it
+/// is not [`Clone`], while the wrapped [`VssClient`] may be shared via [`Self::inner`].
+///
+/// **Caution**: This must not be used from within an async runtime, as blocking on the runtime of
+/// the wrapper from within another one panics.
+///
+/// [`VssClientBuilder`]: crate::client::VssClientBuilder
+pub struct VssClientBlockingWrapper<R>
+where
+	R: RetryPolicy<E = VssError>,
+{
+	client: VssClient<R>,
+	runtime: Runtime,
+}
+
+impl<R: RetryPolicy<E = VssError>> VssClientBlockingWrapper<R> {
+	/// Constructs a new instance wrapping the given `client`.
+	///
+	/// Returns [`VssError::InternalError`] if the runtime could not be created.
+	pub fn new(client: VssClient<R>) -> Result<Self, VssError> {
+		let runtime = Builder::new_current_thread().enable_all().build().map_err(|e| {
+			VssError::InternalError {
+				message: format!("Failed to create runtime: {}", e),
+				source: Some(Box::new(e)),
+				status: None,
+				request_id: None,
+			}
+		})?;
+		Ok(Self { client, runtime })
+	}
+
+	/// Returns the wrapped [`VssClient`].
+	pub fn inner(&self) -> &VssClient<R> {
+		&self.client
+	}
+
+	/// Fetches a value against a given `key` in `request`, blocking until done.
+	///
+	/// Please refer to [`VssClient::get_object`].
+	pub fn get_object(&self, request: &GetObjectRequest) -> Result<GetObjectResponse, VssError> {
+		self.runtime.block_on(self.client.get_object(request))
+	}
+
+	/// Writes multiple [`PutObjectRequest::transaction_items`] as part of a single transaction,
+	/// blocking until done.
+	///
+	/// Please refer to [`VssClient::put_object`].
+	pub fn put_object(&self, request: &PutObjectRequest) -> Result<PutObjectResponse, VssError> {
+		self.runtime.block_on(self.client.put_object(request))
+	}
+
+	/// Deletes the given `key` and `value` in `request`, blocking until done.
+	///
+	/// Please refer to [`VssClient::delete_object`].
+	pub fn delete_object(
+		&self, request: &DeleteObjectRequest,
+	) -> Result<DeleteObjectResponse, VssError> {
+		self.runtime.block_on(self.client.delete_object(request))
+	}
+
+	/// Lists keys and their corresponding version for a given [`ListKeyVersionsRequest::store_id`],
+	/// blocking until done.
+	///
+	/// Please refer to [`VssClient::list_key_versions`].
+	pub fn list_key_versions(
+		&self, request: &ListKeyVersionsRequest,
+	) -> Result<ListKeyVersionsResponse, VssError> {
+		self.runtime.block_on(self.client.list_key_versions(request))
+	}
+}
+
+impl<R: RetryPolicy<E = VssError>> fmt::Debug for VssClientBlockingWrapper<R> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("VssClientBlockingWrapper")
+			.field("base_url", &self.client.base_url())
+			.finish_non_exhaustive()
+	}
+}
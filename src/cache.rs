@@ -0,0 +1,313 @@
+use async_trait::async_trait;
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::client::VssApi;
+use crate::error::VssError;
+use crate::types::{
+	DeleteObjectRequest, DeleteObjectResponse, GetObjectRequest, GetObjectResponse, KeyValue,
+	ListKeyVersionsRequest, ListKeyVersionsResponse, PutObjectRequest, PutObjectResponse,
+};
+
+// The version which skips key-level version checks, i.e. results in a non-conditional write.
+const NON_CONDITIONAL_VERSION: i64 = -1;
+
+/// A wrapper around a [`VssApi`] implementation, caching the values returned by
+/// [`VssApi::get_object`] in memory.
+///
+/// Up to `capacity` values are cached by store id and key, along with their version, evicting the
+/// least recently used one once full. Cached values are served for up to `ttl` after they were
+/// fetched, without a request to the wrapped implementation. Keys missing on the server are not
+/// cached.
+///
+/// Successful writes via [`VssApi::put_object`] update the cached values of the keys they write,
+/// i.e. cache the written value with its incremented version, unless written non-conditionally,
+/// as their new version isn't known then. All other keys affected by writes, including all keys of
+/// failed writes, e.g. due to a conflict, are invalidated, as are keys deleted via
+/// [`VssApi::delete_object`]. Writes by other clients are not observed, hence cached values may be
+/// stale for up to `ttl`, unless observed via [`VssApi::list_key_versions`]: a listed version
+/// matching the cached one renews its `ttl`, while a cached value of any other version is
+/// invalidated.
+///
+/// Clones share the same cache and wrapped implementation.
+///
+/// If the `metrics` feature is enabled, cache hits and misses are counted in
+/// `metrics::CACHE_HITS_TOTAL` and `metrics::CACHE_MISSES_TOTAL`.
+pub struct CachedVssClient<C: VssApi> {
+	inner: Arc<C>,
+	ttl: Duration,
+	cache: Arc<Mutex<ValueCache>>,
+}
+
+// The cached values and when they were fetched, by store id and key.
+type ValueCache = LruCache<(String, String), (KeyValue, Instant)>;
+
+impl<C: VssApi> CachedVssClient<C> {
+	/// Constructs a new instance wrapping `inner`, caching up to `capacity` values for `ttl` each.
+	///
+	/// The `capacity` is at least `1`.
+	pub fn new(inner: C, capacity: usize, ttl: Duration) -> Self {
+		let capacity = NonZeroUsize::new(capacity.max(1)).unwrap();
+		Self { inner: Arc::new(inner), ttl, cache: Arc::new(Mutex::new(LruCache::new(capacity))) }
+	}
+
+	/// Returns the wrapped [`VssApi`] implementation.
+	pub fn inner(&self) -> &C {
+		&self.inner
+	}
+
+	/// Removes the cached value of the given `key` in the given `store_id`, if any, e.g. as it was
+	/// written by another client.
+	pub fn invalidate(&self, store_id: &str, key: &str) {
+		self.invalidate_keys(store_id, std::iter::once(key));
+	}
+
+	/// Removes all cached values.
+	pub fn clear(&self) {
+		self.cache.lock().unwrap().clear();
+	}
+
+	fn cached_value(&self, store_id: &str, key: &str) -> Option<KeyValue> {
+		let mut cache = self.cache.lock().unwrap();
+		let cache_key = (store_id.to_string(), key.to_string());
+		let value = match cache.get(&cache_key) {
+			Some((value, fetched_at)) if fetched_at.elapsed() < self.ttl => Some(value.clone()),
+			Some(_) => {
+				cache.pop(&cache_key);
+				None
+			},
+			None => None,
+		};
+		#[cfg(feature = "metrics")]
+		crate::metrics::record_cache_lookup(value.is_some());
+		value
+	}
+
+	fn invalidate_keys<'a>(&self, store_id: &str, keys: impl Iterator<Item = &'a str>) {
+		let mut cache = self.cache.lock().unwrap();
+		for key in keys {
+			cache.pop(&(store_id.to_string(), key.to_string()));
+		}
+	}
+
+	// Caches the values written by the successful `request`, invalidating all other affected keys.
+	fn write_through(&self, request: &PutObjectRequest) {
+		let mut cache = self.cache.lock().unwrap();
+		let now = Instant::now();
+		for item in &request.transaction_items {
+			let cache_key = (request.store_id.clone(), item.key.clone());
+			if item.version == NON_CONDITIONAL_VERSION {
+				cache.pop(&cache_key);
+			} else {
+				let value = KeyValue { version: item.version + 1, ..item.clone() };
+				cache.put(cache_key, (value, now));
+			}
+		}
+		for item in &request.delete_items {
+			cache.pop(&(request.store_id.clone(), item.key.clone()));
+		}
+	}
+
+	// Renews the cached values of the listed versions, invalidating those of other versions.
+	fn observe_versions(&self, store_id: &str, key_versions: &[KeyValue]) {
+		let mut cache = self.cache.lock().unwrap();
+		let now = Instant::now();
+		for key_version in key_versions {
+			let cache_key = (store_id.to_string(), key_version.key.clone());
+			let is_stale = match cache.peek_mut(&cache_key) {
+				Some((value, fetched_at)) if value.version == key_version.version => {
+					*fetched_at = now;
+					false
+				},
+				Some(_) => true,
+				None => false,
+			};
+			if is_stale {
+				cache.pop(&cache_key);
+			}
+		}
+	}
+}
+
+impl<C: VssApi> Clone for CachedVssClient<C> {
+	fn clone(&self) -> Self {
+		Self { inner: Arc::clone(&self.inner), ttl: self.ttl, cache: Arc::clone(&self.cache) }
+	}
+}
+
+#[async_trait]
+impl<C: VssApi> VssApi for CachedVssClient<C> {
+	async fn get_object(&self, request: &GetObjectRequest) -> Result<GetObjectResponse, VssError> {
+		if let Some(value) = self.cached_value(&request.store_id, &request.key) {
+			return Ok(GetObjectResponse { value: Some(value) });
+		}
+		let response = self.inner.get_object(request).await?;
+		if let Some(value) = &response.value {
+			let cache_key = (request.store_id.clone(), request.key.clone());
+			self.cache.lock().unwrap().put(cache_key, (value.clone(), Instant::now()));
+		}
+		Ok(response)
+	}
+
+	async fn put_object(&self, request: &PutObjectRequest) -> Result<PutObjectResponse, VssError> {
+		let result = self.inner.put_object(request).await;
+		if result.is_ok() {
+			self.write_through(request);
+		} else {
+			let keys = request.transaction_items.iter().chain(request.delete_items.iter());
+			self.invalidate_keys(&request.store_id, keys.map(|item| item.key.as_str()));
+		}
+		result
+	}
+
+	async fn delete_object(
+		&self, request: &DeleteObjectRequest,
+	) -> Result<DeleteObjectResponse, VssError> {
+		let result = self.inner.delete_object(request).await;
+		let keys = request.key_value.iter().map(|key_value| key_value.key.as_str());
+		self.invalidate_keys(&request.store_id, keys);
+		result
+	}
+
+	async fn list_key_versions(
+		&self, request: &ListKeyVersionsRequest,
+	) -> Result<ListKeyVersionsResponse, VssError> {
+		let response = self.inner.list_key_versions(request).await?;
+		self.observe_versions(&request.store_id, &response.key_versions);
+		Ok(response)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_utils::{get_request, put_request, InMemoryVssClient};
+
+	async fn get_value(client: &impl VssApi, key: &str) -> Vec<u8> {
+		client.get_object(&get_request(key)).await.unwrap().value.unwrap().value
+	}
+
+	#[tokio::test]
+	async fn serves_reads_from_cache_until_invalidated() {
+		let client = CachedVssClient::new(InMemoryVssClient::new(), 2, Duration::from_secs(60));
+		client.put_object(&put_request("k1", 0, b"k1v1")).await.unwrap();
+		assert_eq!(get_value(&client, "k1").await, b"k1v1".to_vec());
+
+		// Writes bypassing the cache aren't observed.
+		client.inner().put_object(&put_request("k1", 1, b"k1v2")).await.unwrap();
+		assert_eq!(get_value(&client, "k1").await, b"k1v1".to_vec());
+
+		// Writes via the cache invalidate the affected keys.
+		client.put_object(&put_request("k1", 2, b"k1v3")).await.unwrap();
+		assert_eq!(get_value(&client, "k1").await, b"k1v3".to_vec());
+
+		let delete_request = DeleteObjectRequest {
+			store_id: "store".to_string(),
+			key_value: Some(KeyValue { key: "k1".to_string(), version: 3, value: vec![] }),
+		};
+		client.delete_object(&delete_request).await.unwrap();
+		let result = client.get_object(&get_request("k1")).await;
+		assert!(matches!(result, Err(VssError::NoSuchKeyError { .. })));
+	}
+
+	#[tokio::test]
+	async fn evicts_expired_and_least_recently_used_values() {
+		let ttl = Duration::from_millis(50);
+		let client = CachedVssClient::new(InMemoryVssClient::new(), 2, ttl);
+		for key in ["k1", "k2", "k3"] {
+			client.put_object(&put_request(key, 0, b"v1")).await.unwrap();
+		}
+		get_value(&client, "k1").await;
+		get_value(&client, "k2").await;
+		get_value(&client, "k1").await;
+		// Caching `k3` evicts `k2`, being the least recently used.
+		get_value(&client, "k3").await;
+		for key in ["k1", "k2", "k3"] {
+			client.inner().put_object(&put_request(key, 1, b"v2")).await.unwrap();
+		}
+		assert_eq!(get_value(&client, "k1").await, b"v1".to_vec());
+		assert_eq!(get_value(&client, "k2").await, b"v2".to_vec());
+
+		// Expired values are fetched again.
+		tokio::time::sleep(ttl).await;
+		assert_eq!(get_value(&client, "k1").await, b"v2".to_vec());
+	}
+
+	#[tokio::test]
+	async fn writes_through_and_observes_listed_versions() {
+		let client = CachedVssClient::new(InMemoryVssClient::new(), 10, Duration::from_secs(60));
+		client.put_object(&put_request("k1", 0, b"k1v1")).await.unwrap();
+		client.put_object(&put_request("k2", 0, b"k2v1")).await.unwrap();
+
+		// Written values are cached along with their new version, without fetching them.
+		client.inner().put_object(&put_request("k1", 1, b"other")).await.unwrap();
+		let value = client.get_object(&get_request("k1")).await.unwrap().value.unwrap();
+		assert_eq!((value.version, value.value), (1, b"k1v1".to_vec()));
+
+		// Listing reveals `k1` was written by another client, while `k2` remains cached.
+		let list_request = ListKeyVersionsRequest {
+			store_id: "store".to_string(),
+			key_prefix: None,
+			page_size: None,
+			page_token: None,
+		};
+		client.list_key_versions(&list_request).await.unwrap();
+		let value = client.get_object(&get_request("k1")).await.unwrap().value.unwrap();
+		assert_eq!((value.version, value.value), (2, b"other".to_vec()));
+		client.inner().put_object(&put_request("k2", 1, b"k2v2")).await.unwrap();
+		assert_eq!(get_value(&client, "k2").await, b"k2v1".to_vec());
+
+		// Explicitly invalidated values are fetched again.
+		client.invalidate("store", "k2");
+		assert_eq!(get_value(&client, "k2").await, b"k2v2".to_vec());
+	}
+
+	#[tokio::test]
+	async fn invalidates_stale_value_on_conflict() {
+		let client = CachedVssClient::new(InMemoryVssClient::new(), 10, Duration::from_secs(60));
+		client.put_object(&put_request("k1", 0, b"k1v1")).await.unwrap();
+		client.inner().put_object(&put_request("k1", 1, b"other")).await.unwrap();
+		assert_eq!(get_value(&client, "k1").await, b"k1v1".to_vec());
+
+		// The write conditioned on the stale version conflicts, invalidating it.
+		let result = client.put_object(&put_request("k1", 1, b"k1v2")).await;
+		assert!(matches!(result, Err(VssError::ConflictError { .. })));
+		let value = client.get_object(&get_request("k1")).await.unwrap().value.unwrap();
+		assert_eq!((value.version, value.value), (2, b"other".to_vec()));
+	}
+
+	#[tokio::test]
+	async fn shares_cache_across_clones() {
+		fn assert_send_sync<T: Send + Sync>() {}
+		assert_send_sync::<CachedVssClient<InMemoryVssClient>>();
+
+		let client = CachedVssClient::new(InMemoryVssClient::new(), 100, Duration::from_secs(60));
+		let tasks: Vec<_> = (0..10)
+			.map(|i| {
+				let client = client.clone();
+				tokio::spawn(async move {
+					let key = format!("k{}", i);
+					for version in 0..5 {
+						let value = format!("{}v{}", key, version + 1).into_bytes();
+						client.put_object(&put_request(&key, version, &value)).await.unwrap();
+						assert_eq!(get_value(&client, &key).await, value);
+					}
+				})
+			})
+			.collect();
+		for task in tasks {
+			task.await.unwrap();
+		}
+
+		// All values were cached via the shared cache, i.e. are served without fetching them.
+		for i in 0..10 {
+			let key = format!("k{}", i);
+			client.inner().put_object(&put_request(&key, 5, b"other")).await.unwrap();
+			assert_eq!(get_value(&client, &key).await, format!("{}v5", key).into_bytes());
+		}
+		client.clear();
+		assert_eq!(get_value(&client, "k0").await, b"other".to_vec());
+	}
+}
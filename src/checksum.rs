@@ -0,0 +1,151 @@
+use async_trait::async_trait;
+use bitcoin_hashes::{sha256, Hash};
+
+use crate::client::VssApi;
+use crate::error::VssError;
+use crate::types::{
+	DeleteObjectRequest, DeleteObjectResponse, GetObjectRequest, GetObjectResponse,
+	ListKeyVersionsRequest, ListKeyVersionsResponse, PutObjectRequest, PutObjectResponse,
+};
+
+// Identifies values prefixed by the SHA-256 checksum of their payload.
+const SHA256_CHECKSUM_FORMAT: u8 = 1;
+const SHA256_CHECKSUM_LENGTH: usize = 32;
+
+/// A wrapper around a [`VssApi`] implementation, embedding a checksum in each value to detect
+/// values which were corrupted in transit or at rest.
+///
+/// Values of [`PutObjectRequest::transaction_items`] are prefixed by the SHA-256 checksum of their
+/// payload before being sent to the server, which is verified and stripped on receipt of a
+/// [`GetObjectResponse::value`]. A value failing verification results in a
+/// [`VssError::ChecksumMismatchError`], naming its key, such that callers can fall back to another
+/// backup.
+///
+/// To distinguish corrupted values from tampered ones if they are also encrypted, it should be
+/// wrapped by [`EncryptedVssClient`]. That way, the checksum covers the encrypted value, such that
+/// it is verified before the value is decrypted.
+///
+/// [`EncryptedVssClient`]: crate::encryption::EncryptedVssClient
+pub struct ChecksummedVssClient<C: VssApi> {
+	inner: C,
+}
+
+impl<C: VssApi> ChecksummedVssClient<C> {
+	/// Constructs a new instance wrapping `inner`.
+	pub fn new(inner: C) -> Self {
+		Self { inner }
+	}
+
+	/// Returns the wrapped [`VssApi`] implementation.
+	pub fn inner(&self) -> &C {
+		&self.inner
+	}
+}
+
+#[async_trait]
+impl<C: VssApi> VssApi for ChecksummedVssClient<C> {
+	async fn get_object(&self, request: &GetObjectRequest) -> Result<GetObjectResponse, VssError> {
+		let mut response = self.inner.get_object(request).await?;
+		if let Some(key_value) = response.value.as_mut() {
+			key_value.value = verify_checksum(&key_value.value, &request.key)?;
+		}
+		Ok(response)
+	}
+
+	async fn put_object(&self, request: &PutObjectRequest) -> Result<PutObjectResponse, VssError> {
+		let mut request = request.clone();
+		for item in request.transaction_items.iter_mut() {
+			item.value = with_checksum(&item.value);
+		}
+		self.inner.put_object(&request).await
+	}
+
+	async fn delete_object(
+		&self, request: &DeleteObjectRequest,
+	) -> Result<DeleteObjectResponse, VssError> {
+		self.inner.delete_object(request).await
+	}
+
+	async fn list_key_versions(
+		&self, request: &ListKeyVersionsRequest,
+	) -> Result<ListKeyVersionsResponse, VssError> {
+		self.inner.list_key_versions(request).await
+	}
+}
+
+fn with_checksum(payload: &[u8]) -> Vec<u8> {
+	let mut value = Vec::with_capacity(1 + SHA256_CHECKSUM_LENGTH + payload.len());
+	value.push(SHA256_CHECKSUM_FORMAT);
+	value.extend_from_slice(sha256::Hash::hash(payload).as_byte_array());
+	value.extend_from_slice(payload);
+	value
+}
+
+fn verify_checksum(value: &[u8], key: &str) -> Result<Vec<u8>, VssError> {
+	match value.split_first() {
+		Some((&SHA256_CHECKSUM_FORMAT, rest)) if rest.len() >= SHA256_CHECKSUM_LENGTH => {
+			let (checksum, payload) = rest.split_at(SHA256_CHECKSUM_LENGTH);
+			if sha256::Hash::hash(payload).as_byte_array()[..] == *checksum {
+				Ok(payload.to_vec())
+			} else {
+				Err(VssError::ChecksumMismatchError(format!("Invalid checksum for key: {}", key)))
+			}
+		},
+		_ => Err(VssError::ChecksumMismatchError(format!("Missing checksum for key: {}", key))),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::encryption::EncryptedVssClient;
+	use crate::test_utils::{get_request, put_request, InMemoryVssClient, RandomEntropySource};
+
+	// Flips a byte of the value stored against `key`, bypassing all wrappers.
+	async fn corrupt(client: &InMemoryVssClient, key: &str) {
+		let mut stored = client.get_object(&get_request(key)).await.unwrap().value.unwrap();
+		let last = stored.value.len() - 1;
+		stored.value[last] ^= 1;
+		client.put_object(&put_request(key, stored.version, &stored.value)).await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn detects_corrupted_values() {
+		let client = ChecksummedVssClient::new(InMemoryVssClient::new());
+		client.put_object(&put_request("k1", 0, b"monitor")).await.unwrap();
+		let value = client.get_object(&get_request("k1")).await.unwrap().value.unwrap();
+		assert_eq!(value.value, b"monitor".to_vec());
+
+		corrupt(client.inner(), "k1").await;
+		let error = client.get_object(&get_request("k1")).await.unwrap_err();
+		assert!(matches!(error, VssError::ChecksumMismatchError(..)));
+		assert!(error.to_string().contains("k1"));
+
+		// Values written without a checksum fail verification as well.
+		client.inner().put_object(&put_request("k2", 0, b"")).await.unwrap();
+		let error = client.get_object(&get_request("k2")).await.unwrap_err();
+		assert!(matches!(error, VssError::ChecksumMismatchError(..)));
+	}
+
+	#[tokio::test]
+	async fn distinguishes_corrupted_from_undecryptable_values() {
+		let client = EncryptedVssClient::new(
+			ChecksummedVssClient::new(InMemoryVssClient::new()),
+			[7u8; 32],
+			RandomEntropySource,
+		);
+		client.put_object(&put_request("k1", 0, b"monitor")).await.unwrap();
+		let value = client.get_object(&get_request("k1")).await.unwrap().value.unwrap();
+		assert_eq!(value.value, b"monitor".to_vec());
+
+		corrupt(client.inner().inner(), "k1").await;
+		let error = client.get_object(&get_request("k1")).await.unwrap_err();
+		assert!(matches!(error, VssError::ChecksumMismatchError(..)));
+		assert!(error.to_string().contains("k1"));
+
+		// A value with a valid checksum still fails to decrypt if it wasn't encrypted by us.
+		client.inner().put_object(&put_request("k1", 2, b"forged")).await.unwrap();
+		let error = client.get_object(&get_request("k1")).await.unwrap_err();
+		assert!(matches!(error, VssError::DecryptionError(..)));
+	}
+}
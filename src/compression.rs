@@ -0,0 +1,186 @@
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use prost::bytes::Bytes;
+use reqwest::header::{HeaderMap, CONTENT_ENCODING};
+use std::io::{self, Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::error::VssError;
+
+const GZIP: &str = "gzip";
+
+// In `CompressionMode::Auto`, request bodies smaller than this are sent uncompressed, as
+// compressing them is unlikely to pay off.
+const AUTO_COMPRESSION_THRESHOLD: usize = 1024;
+
+/// Defines whether request bodies sent to the VSS server are compressed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CompressionMode {
+	/// Request bodies are sent uncompressed.
+	#[default]
+	None,
+	/// All request bodies are sent gzip-compressed.
+	Gzip,
+	/// Request bodies of at least 1 KiB are sent gzip-compressed.
+	///
+	/// If the server rejects a compressed request with HTTP `415 Unsupported Media Type`, the
+	/// request is re-sent uncompressed, and compression is disabled for all subsequent requests.
+	Auto,
+}
+
+/// The gzip compression level, ranging from `1` (fastest) to `9` (best compression).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompressionLevel(u32);
+
+impl CompressionLevel {
+	/// Constructs a new compression level.
+	///
+	/// Returns [`VssError::InvalidArgumentError`] if `level` is not within `1..=9`.
+	pub fn new(level: u32) -> Result<Self, VssError> {
+		if (1..=9).contains(&level) {
+			Ok(Self(level))
+		} else {
+			Err(VssError::InvalidArgumentError(format!(
+				"Compression level must be within 1..=9, found: {}",
+				level
+			)))
+		}
+	}
+
+	/// Returns the compression level.
+	pub fn level(&self) -> u32 {
+		self.0
+	}
+}
+
+impl Default for CompressionLevel {
+	fn default() -> Self {
+		Self(6)
+	}
+}
+
+// Compresses request bodies according to the configured `CompressionMode`.
+#[derive(Clone, Debug)]
+pub(crate) struct RequestCompressor {
+	mode: CompressionMode,
+	level: CompressionLevel,
+	// Set once the server rejected a compressed request in `CompressionMode::Auto`.
+	unsupported: Arc<AtomicBool>,
+}
+
+impl RequestCompressor {
+	pub(crate) fn new(mode: CompressionMode, level: CompressionLevel) -> Self {
+		Self { mode, level, unsupported: Arc::new(AtomicBool::new(false)) }
+	}
+
+	pub(crate) fn is_enabled(&self) -> bool {
+		self.mode != CompressionMode::None
+	}
+
+	pub(crate) fn should_compress(&self, request_body: &[u8]) -> bool {
+		match self.mode {
+			CompressionMode::None => false,
+			CompressionMode::Gzip => true,
+			CompressionMode::Auto => {
+				request_body.len() >= AUTO_COMPRESSION_THRESHOLD
+					&& !self.unsupported.load(Ordering::Relaxed)
+			},
+		}
+	}
+
+	// Called once the server rejected a compressed request as unsupported. Returns `true` if the
+	// request should be re-sent uncompressed.
+	pub(crate) fn on_unsupported(&self) -> bool {
+		if self.mode == CompressionMode::Auto {
+			self.unsupported.store(true, Ordering::Relaxed);
+			true
+		} else {
+			false
+		}
+	}
+
+	pub(crate) fn compress(&self, request_body: &[u8]) -> Result<Vec<u8>, VssError> {
+		gzip(request_body, self.level).map_err(|e| VssError::InternalError {
+			message: format!("Failed to compress request body: {}", e),
+			source: Some(Box::new(e)),
+			status: None,
+			request_id: None,
+		})
+	}
+}
+
+pub(crate) fn gzip(data: &[u8], level: CompressionLevel) -> io::Result<Vec<u8>> {
+	let mut encoder = GzEncoder::new(Vec::new(), Compression::new(level.0));
+	encoder.write_all(data)?;
+	encoder.finish()
+}
+
+pub(crate) fn gunzip(data: &[u8]) -> io::Result<Vec<u8>> {
+	let mut decompressed = Vec::new();
+	GzDecoder::new(data).read_to_end(&mut decompressed)?;
+	Ok(decompressed)
+}
+
+// Decompresses the response `payload` if it is gzip-encoded according to its `headers`.
+pub(crate) fn decompress_response(headers: &HeaderMap, payload: Bytes) -> Result<Bytes, VssError> {
+	let is_gzip = headers
+		.get(CONTENT_ENCODING)
+		.and_then(|value| value.to_str().ok())
+		.map_or(false, |value| value.trim().eq_ignore_ascii_case(GZIP));
+	if !is_gzip {
+		return Ok(payload);
+	}
+	let decompressed = gunzip(&payload).map_err(|e| VssError::InternalError {
+		message: format!("Failed to decompress response body: {}", e),
+		source: Some(Box::new(e)),
+		status: None,
+		request_id: None,
+	})?;
+	Ok(decompressed.into())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use reqwest::header::HeaderValue;
+
+	#[test]
+	fn compresses_and_decompresses() {
+		let compressor = RequestCompressor::new(CompressionMode::Gzip, CompressionLevel::default());
+		let body = vec![42u8; 4096];
+		let compressed = compressor.compress(&body).unwrap();
+		assert!(compressed.len() < body.len());
+
+		let mut headers = HeaderMap::new();
+		headers.insert(CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+		assert_eq!(decompress_response(&headers, compressed.into()).unwrap(), body);
+
+		// Responses without a gzip content encoding are passed through.
+		let payload = Bytes::from_static(b"payload");
+		assert_eq!(decompress_response(&HeaderMap::new(), payload.clone()).unwrap(), payload);
+	}
+
+	#[test]
+	fn auto_mode_compresses_large_bodies_until_unsupported() {
+		let compressor = RequestCompressor::new(CompressionMode::Auto, CompressionLevel::default());
+		assert!(!compressor.should_compress(&[0u8; 16]));
+		assert!(compressor.should_compress(&[0u8; AUTO_COMPRESSION_THRESHOLD]));
+
+		assert!(compressor.on_unsupported());
+		assert!(!compressor.should_compress(&[0u8; AUTO_COMPRESSION_THRESHOLD]));
+
+		// Only `CompressionMode::Auto` falls back to uncompressed requests.
+		let compressor = RequestCompressor::new(CompressionMode::Gzip, CompressionLevel::default());
+		assert!(!compressor.on_unsupported());
+		assert!(compressor.should_compress(&[0u8; 16]));
+	}
+
+	#[test]
+	fn validates_compression_level() {
+		assert_eq!(CompressionLevel::new(9).unwrap().level(), 9);
+		assert!(matches!(CompressionLevel::new(0), Err(VssError::InvalidArgumentError(..))));
+		assert!(matches!(CompressionLevel::new(10), Err(VssError::InvalidArgumentError(..))));
+	}
+}
@@ -0,0 +1,163 @@
+use async_trait::async_trait;
+use prost::Message;
+
+use crate::client::VssApi;
+#[cfg(feature = "gzip")]
+use crate::compression::CompressionLevel;
+use crate::envelope::Storable;
+use crate::error::VssError;
+use crate::types::{
+	DeleteObjectRequest, DeleteObjectResponse, GetObjectRequest, GetObjectResponse,
+	ListKeyVersionsRequest, ListKeyVersionsResponse, PutObjectRequest, PutObjectResponse,
+};
+use crate::util::storable_builder::{EntropySource, StorableBuilder};
+
+/// A wrapper around a [`VssApi`] implementation, transparently encrypting values client-side.
+///
+/// Values of [`PutObjectRequest::transaction_items`] are encrypted before being sent to the
+/// server, and values of [`GetObjectResponse::value`] are decrypted on receipt, such that the
+/// server operator never has access to their plaintext. Encryption is performed using
+/// ChaCha20-Poly1305 via [`StorableBuilder`], with a fresh random nonce drawn from the
+/// [`EntropySource`] for every write.
+///
+/// Each value is bound to the store id and key it is stored under, such that values swapped between
+/// keys server-side fail to decrypt. Store ids and keys are sent in plaintext, such that the server
+/// can still look them up. Refer to [`KeyObfuscator`] for obfuscating keys.
+///
+/// [`KeyObfuscator`]: crate::util::key_obfuscator::KeyObfuscator
+pub struct EncryptedVssClient<C: VssApi, T: EntropySource + Send + Sync> {
+	inner: C,
+	storable_builder: StorableBuilder<T>,
+}
+
+impl<C: VssApi, T: EntropySource + Send + Sync> EncryptedVssClient<C, T> {
+	/// Constructs a new instance wrapping `inner`, encrypting values with the given 256-bit
+	/// `data_encryption_key`.
+	pub fn new(inner: C, data_encryption_key: [u8; 32], entropy_source: T) -> Self {
+		Self { inner, storable_builder: StorableBuilder::new(data_encryption_key, entropy_source) }
+	}
+
+	/// Sets whether legacy values, which were encrypted without being bound to their store id and
+	/// key, can still be decrypted. Defaults to `true`.
+	///
+	/// Please refer to [`StorableBuilder::accept_legacy_storables`].
+	pub fn accept_legacy_values(mut self, accept_legacy_values: bool) -> Self {
+		self.storable_builder = self.storable_builder.accept_legacy_storables(accept_legacy_values);
+		self
+	}
+
+	/// Sets values of at least `min_size` bytes to be gzip-compressed before being encrypted.
+	///
+	/// Please refer to [`StorableBuilder::compress_values`].
+	#[cfg(feature = "gzip")]
+	pub fn compress_values(mut self, level: CompressionLevel, min_size: usize) -> Self {
+		self.storable_builder = self.storable_builder.compress_values(level, min_size);
+		self
+	}
+
+	/// Returns the wrapped [`VssApi`] implementation.
+	pub fn inner(&self) -> &C {
+		&self.inner
+	}
+
+	fn decrypt(&self, value: &[u8], store_id: &str, key: &str) -> Result<Vec<u8>, VssError> {
+		let storable = Storable::decode(value)
+			.map_err(|e| VssError::DecryptionError(format!("Invalid Storable: {}", e)))?;
+		let (data, _version) = self
+			.storable_builder
+			.deconstruct(storable, store_id, key)
+			.map_err(|e| VssError::DecryptionError(e.to_string()))?;
+		Ok(data)
+	}
+}
+
+#[async_trait]
+impl<C: VssApi, T: EntropySource + Send + Sync> VssApi for EncryptedVssClient<C, T> {
+	async fn get_object(&self, request: &GetObjectRequest) -> Result<GetObjectResponse, VssError> {
+		let mut response = self.inner.get_object(request).await?;
+		if let Some(key_value) = response.value.as_mut() {
+			key_value.value = self.decrypt(&key_value.value, &request.store_id, &request.key)?;
+		}
+		Ok(response)
+	}
+
+	async fn put_object(&self, request: &PutObjectRequest) -> Result<PutObjectResponse, VssError> {
+		let mut request = request.clone();
+		for item in request.transaction_items.iter_mut() {
+			let value = std::mem::take(&mut item.value);
+			let storable =
+				self.storable_builder.build(value, item.version, &request.store_id, &item.key);
+			item.value = storable.encode_to_vec();
+		}
+		self.inner.put_object(&request).await
+	}
+
+	async fn delete_object(
+		&self, request: &DeleteObjectRequest,
+	) -> Result<DeleteObjectResponse, VssError> {
+		self.inner.delete_object(request).await
+	}
+
+	async fn list_key_versions(
+		&self, request: &ListKeyVersionsRequest,
+	) -> Result<ListKeyVersionsResponse, VssError> {
+		self.inner.list_key_versions(request).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_utils::{get_request, put_request, InMemoryVssClient, RandomEntropySource};
+	use crate::types::KeyValue;
+
+	#[tokio::test]
+	async fn encrypts_and_decrypts_values() {
+		let client =
+			EncryptedVssClient::new(InMemoryVssClient::new(), [7u8; 32], RandomEntropySource);
+		client.put_object(&put_request("k1", 0, b"secret")).await.unwrap();
+
+		// The server only ever sees the encrypted value, but the key in plaintext.
+		let stored = client.inner().get_object(&get_request("k1")).await.unwrap().value.unwrap();
+		assert_eq!(stored.key, "k1");
+		assert_ne!(stored.value, b"secret".to_vec());
+
+		let value = client.get_object(&get_request("k1")).await.unwrap().value.unwrap();
+		assert_eq!(
+			value,
+			KeyValue { key: "k1".to_string(), version: 1, value: b"secret".to_vec() }
+		);
+	}
+
+	#[tokio::test]
+	async fn fails_to_decrypt_with_wrong_key_or_plaintext_value() {
+		let client =
+			EncryptedVssClient::new(InMemoryVssClient::new(), [7u8; 32], RandomEntropySource);
+		client.put_object(&put_request("k1", 0, b"secret")).await.unwrap();
+		client.inner().put_object(&put_request("k2", 0, b"plaintext")).await.unwrap();
+
+		let result = client.get_object(&get_request("k2")).await;
+		assert!(matches!(result.unwrap_err(), VssError::DecryptionError(..)));
+
+		let other_client = EncryptedVssClient::new(client.inner, [8u8; 32], RandomEntropySource);
+		let result = other_client.get_object(&get_request("k1")).await;
+		assert!(matches!(result.unwrap_err(), VssError::DecryptionError(..)));
+	}
+
+	#[tokio::test]
+	async fn fails_to_decrypt_values_swapped_between_keys() {
+		let client =
+			EncryptedVssClient::new(InMemoryVssClient::new(), [7u8; 32], RandomEntropySource);
+		client.put_object(&put_request("k1", 0, b"secret1")).await.unwrap();
+		client.put_object(&put_request("k2", 0, b"secret2")).await.unwrap();
+
+		// Move the encrypted value of `k1` to `k2` server-side.
+		let stored = client.inner().get_object(&get_request("k1")).await.unwrap().value.unwrap();
+		client.inner().put_object(&put_request("k2", 1, &stored.value)).await.unwrap();
+
+		let result = client.get_object(&get_request("k2")).await;
+		assert!(matches!(result.unwrap_err(), VssError::DecryptionError(..)));
+		let value = client.get_object(&get_request("k1")).await.unwrap().value.unwrap();
+		assert_eq!(value.value, b"secret1".to_vec());
+	}
+}
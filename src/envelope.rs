@@ -0,0 +1,49 @@
+/// Represents a storable object that can be serialized and stored as `value` in `PutObjectRequest`.
+/// Wire-compatible with `Storable` of `vss.proto`.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Storable {
+	/// Represents an encrypted and serialized `PlaintextBlob`.
+	#[prost(bytes = "vec", tag = "1")]
+	pub data: ::prost::alloc::vec::Vec<u8>,
+	/// Represents encryption related metadata
+	#[prost(message, optional, tag = "2")]
+	pub encryption_metadata: ::core::option::Option<EncryptionMetadata>,
+}
+/// Represents encryption related metadata.
+/// Extends `EncryptionMetadata` of `vss.proto` by `key_id`.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EncryptionMetadata {
+	/// The encryption algorithm used for encrypting the `PlaintextBlob`.
+	#[prost(string, tag = "1")]
+	pub cipher_format: ::prost::alloc::string::String,
+	/// The nonce used for encryption. Nonce is a random or unique value used to ensure that the same
+	/// plaintext results in different ciphertexts every time it is encrypted.
+	#[prost(bytes = "vec", tag = "2")]
+	pub nonce: ::prost::alloc::vec::Vec<u8>,
+	/// The authentication tag used for encryption. It provides integrity and authenticity assurance
+	/// for the encrypted data.
+	#[prost(bytes = "vec", tag = "3")]
+	pub tag: ::prost::alloc::vec::Vec<u8>,
+	/// The identifier of the key used for encryption, allowing for key rotation. Empty for values
+	/// encrypted without a key identifier.
+	#[prost(string, tag = "4")]
+	pub key_id: ::prost::alloc::string::String,
+}
+/// Represents a data blob, which is encrypted, serialized and later used in `Storable.data`.
+/// Extends `PlaintextBlob` of `vss.proto` by `compression`.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PlaintextBlob {
+	/// The unencrypted value.
+	#[prost(bytes = "vec", tag = "1")]
+	pub value: ::prost::alloc::vec::Vec<u8>,
+	/// The version of the value. Can be used by client to verify version integrity.
+	#[prost(int64, tag = "2")]
+	pub version: i64,
+	/// The compression applied to `value` before encryption, e.g. `gzip`. Empty for values stored
+	/// uncompressed.
+	#[prost(string, tag = "3")]
+	pub compression: ::prost::alloc::string::String,
+}
@@ -1,9 +1,11 @@
 use crate::types::{ErrorCode, ErrorResponse};
 use prost::bytes::Bytes;
 use prost::{DecodeError, Message};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
 use reqwest::StatusCode;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
+use std::time::{Duration, SystemTime};
 
 /// When there is an error while writing to VSS storage, the response contains a relevant error code.
 /// A mapping from a VSS server error codes. Refer to [`ErrorResponse`] docs for more
@@ -11,96 +13,988 @@ use std::fmt::{Display, Formatter};
 #[derive(Debug)]
 pub enum VssError {
 	/// Please refer to [`ErrorCode::NoSuchKeyException`].
-	NoSuchKeyError(String),
+	///
+	/// As the VSS server only reports a message, the missing `key` and its `store_id` are attributed
+	/// by the client, e.g. by [`VssClient::get_object`], see [`VssError::key`].
+	///
+	/// [`VssClient::get_object`]: crate::client::VssClient::get_object
+	NoSuchKeyError {
+		/// The message describing the error, as returned by the server.
+		message: String,
+		/// The store the key was requested from, if known.
+		store_id: Option<String>,
+		/// The requested key, if known.
+		key: Option<String>,
+		/// The id of the request which failed, if any, see [`VssError::request_id`].
+		request_id: Option<String>,
+	},
 
 	/// Please refer to [`ErrorCode::InvalidRequestException`].
 	InvalidRequestError(String),
 
 	/// Please refer to [`ErrorCode::ConflictException`].
-	ConflictError(String),
+	///
+	/// As the VSS server only reports a message describing the conflict, the conflicting `key` and
+	/// its versions are only known if they can be attributed unambiguously, i.e. if the failed
+	/// write was for a single key without a [`PutObjectRequest::global_version`].
+	///
+	/// [`PutObjectRequest::global_version`]: crate::types::PutObjectRequest::global_version
+	ConflictError {
+		/// The message describing the conflict, as returned by the server.
+		message: String,
+		/// The key whose write conflicted, if known.
+		key: Option<String>,
+		/// The version of `key` the write was conditioned on, if known.
+		expected_version: Option<i64>,
+		/// The version of `key` currently stored, if provided by the server.
+		current_version: Option<i64>,
+		/// The id of the request which failed, if any, see [`VssError::request_id`].
+		request_id: Option<String>,
+	},
+
+	/// A write via [`VssClient::put_if_absent`] failed, as a key already exists.
+	///
+	/// [`VssClient::put_if_absent`]: crate::client::VssClient::put_if_absent
+	AlreadyExistsError(String),
+
+	/// A write via [`VssClient::compare_and_swap`] kept conflicting with concurrent writes, until
+	/// running out of attempts.
+	///
+	/// [`VssClient::compare_and_swap`]: crate::client::VssClient::compare_and_swap
+	CasConflictError(String),
+
+	/// A write via [`VssClient::put_object_with_global_version`] conflicted, as the global version
+	/// of the store changed since it was read, i.e. the store was written to concurrently.
+	///
+	/// [`VssClient::put_object_with_global_version`]: crate::client::VssClient::put_object_with_global_version
+	GlobalVersionConflictError {
+		/// The message describing the conflict, as returned by the server.
+		message: String,
+		/// The global version the write was conditioned on.
+		expected_global_version: i64,
+		/// The global version of the store, as fetched after the write conflicted.
+		current_global_version: i64,
+		/// The id of the request which failed, if any, see [`VssError::request_id`].
+		request_id: Option<String>,
+	},
 
 	/// Please refer to [`ErrorCode::AuthException`].
+	///
+	/// Also returned for HTTP `401` and `403` responses without a recognizable [`ErrorResponse`],
+	/// e.g. returned by an authenticating proxy in front of the VSS server, and if providing the
+	/// credentials of a request failed, e.g. via a [`VssHeaderProvider`] or an [`AuthProvider`].
+	///
+	/// [`VssHeaderProvider`]: crate::headers::VssHeaderProvider
+	/// [`AuthProvider`]: crate::auth::AuthProvider
 	AuthError(String),
 
 	/// Please refer to [`ErrorCode::InternalServerException`].
-	InternalServerError(String),
+	InternalServerError {
+		/// The message describing the error.
+		message: String,
+		/// The HTTP status of the response, if the error was created from one, see
+		/// [`VssError::http_status`].
+		status: Option<u16>,
+		/// The id of the request which failed, if any, see [`VssError::request_id`].
+		request_id: Option<String>,
+	},
 
 	/// There is an unknown error, it could be a client-side bug, unrecognized error-code, network error
 	/// or something else.
-	InternalError(String),
+	InternalError {
+		/// The message describing the error.
+		message: String,
+		/// The underlying error, e.g. a transport or decoding error, if any.
+		source: Option<Box<dyn Error + Send + Sync>>,
+		/// The HTTP status of the response, if the error was created from one, see
+		/// [`VssError::http_status`].
+		status: Option<u16>,
+		/// The id of the request which failed, if any, see [`VssError::request_id`].
+		request_id: Option<String>,
+	},
+
+	/// The server asked the client to back off, i.e. responded with HTTP `429`, or with HTTP `503`
+	/// and a `Retry-After` header.
+	RateLimitedError {
+		/// The message describing the error.
+		message: String,
+		/// The delay requested by the server via the `Retry-After` header, if any.
+		///
+		/// Please refer to [`RetryPolicy::with_retry_after`] for honoring it.
+		///
+		/// [`RetryPolicy::with_retry_after`]: crate::util::retry::RetryPolicy::with_retry_after
+		retry_after: Option<Duration>,
+		/// The HTTP status of the response, if the error was created from one, see
+		/// [`VssError::http_status`].
+		status: Option<u16>,
+		/// The id of the request which failed, if any, see [`VssError::request_id`].
+		request_id: Option<String>,
+	},
+
+	/// The request timed out before a response was received from the server.
+	TimeoutError {
+		/// The message describing the error.
+		message: String,
+		/// The underlying transport error.
+		source: Option<Box<dyn Error + Send + Sync>>,
+		/// The id of the request which failed, if any, see [`VssError::request_id`].
+		request_id: Option<String>,
+	},
+
+	/// An invalid argument was supplied on the client-side, e.g. while configuring a
+	/// [`VssClient`].
+	///
+	/// [`VssClient`]: crate::client::VssClient
+	InvalidArgumentError(String),
+
+	/// The request was not sent, as the circuit breaker is open after repeated failures.
+	///
+	/// Please refer to [`CircuitBreakerRetryPolicy`].
+	///
+	/// [`CircuitBreakerRetryPolicy`]: crate::util::retry::CircuitBreakerRetryPolicy
+	CircuitOpenError(String),
+
+	/// The TLS configuration of a [`VssClientBuilder`] was invalid, e.g. a client certificate or a
+	/// root certificate could not be parsed.
+	///
+	/// [`VssClientBuilder`]: crate::client::VssClientBuilder
+	TlsConfigurationError(String),
+
+	/// A key or value could not be serialized or deserialized.
+	///
+	/// Please refer to `TypedVssClient`, available with the `typed` feature.
+	SerializationError(String),
+
+	/// A value retrieved from VSS failed checksum verification, i.e. it was corrupted.
+	///
+	/// Please refer to [`ChecksummedVssClient`].
+	///
+	/// [`ChecksummedVssClient`]: crate::checksum::ChecksummedVssClient
+	ChecksumMismatchError(String),
+
+	/// A value stored in chunks could not be reassembled, e.g. as a chunk is missing.
+	///
+	/// Please refer to [`VssClient::get_chunked`].
+	///
+	/// [`VssClient::get_chunked`]: crate::client::VssClient::get_chunked
+	ChunkedValueError(String),
+
+	/// A value retrieved from VSS could not be decrypted, e.g. as it was encrypted with a different
+	/// key or was tampered with.
+	///
+	/// Please refer to [`EncryptedVssClient`].
+	///
+	/// [`EncryptedVssClient`]: crate::encryption::EncryptedVssClient
+	DecryptionError(String),
 }
 
 impl VssError {
 	/// Create new instance of `VssError`
+	///
+	/// Transient server-side failures (HTTP `500`, `502`, `503` and `504`) which don't carry a
+	/// recognizable [`ErrorResponse`], e.g. a `503` returned by a load balancer, are mapped to
+	/// [`VssError::InternalServerError`] so that they can be retried like any other server error.
+	/// Likewise, such `401` and `403` responses are mapped to [`VssError::AuthError`].
 	pub fn new(status: StatusCode, payload: Bytes) -> VssError {
-		match ErrorResponse::decode(&payload[..]) {
-			Ok(error_response) => VssError::from(error_response),
-			Err(e) => {
-				let message = format!(
-					"Unable to decode ErrorResponse from server, HttpStatusCode: {}, DecodeErr: {}",
-					status, e
-				);
-				VssError::InternalError(message)
+		let decode_error = match ErrorResponse::decode(&payload[..]) {
+			// An empty payload, or one without any known field, decodes into an empty
+			// `ErrorResponse`, which doesn't carry any information about the error.
+			Ok(error_response)
+				if error_response.error_code == 0 && error_response.message.is_empty() =>
+			{
+				"Empty ErrorResponse".to_string()
+			},
+			Ok(error_response) => {
+				let error = match VssError::from(error_response) {
+					VssError::InternalError { message, .. }
+						if is_transient_server_error(status) =>
+					{
+						VssError::InternalServerError { message, status: None, request_id: None }
+					},
+					VssError::InternalError { message, .. } if is_auth_failure(status) => {
+						VssError::AuthError(message)
+					},
+					error => error,
+				};
+				return error.with_http_status(status);
+			},
+			Err(e) => e.to_string(),
+		};
+		let message = format!(
+			"Unable to decode ErrorResponse from server, HttpStatusCode: {}, DecodeErr: {}, Body: {}",
+			status,
+			decode_error,
+			body_snippet(&payload)
+		);
+		let http_status = Some(status.as_u16());
+		if is_transient_server_error(status) {
+			VssError::InternalServerError { message, status: http_status, request_id: None }
+		} else if is_auth_failure(status) {
+			VssError::AuthError(message)
+		} else {
+			VssError::InternalError { message, source: None, status: http_status, request_id: None }
+		}
+	}
+
+	// Sets the HTTP `status` of the response the error was created from, if its variant carries it.
+	fn with_http_status(mut self, http_status: StatusCode) -> Self {
+		match &mut self {
+			VssError::InternalServerError { status, .. }
+			| VssError::InternalError { status, .. }
+			| VssError::RateLimitedError { status, .. } => *status = Some(http_status.as_u16()),
+			_ => {},
+		}
+		self
+	}
+
+	// Sets the `request_id` of the request the error resulted from, if its variant carries it.
+	pub(crate) fn with_request_id(mut self, id: &str) -> Self {
+		match &mut self {
+			VssError::NoSuchKeyError { request_id, .. }
+			| VssError::ConflictError { request_id, .. }
+			| VssError::GlobalVersionConflictError { request_id, .. }
+			| VssError::InternalServerError { request_id, .. }
+			| VssError::InternalError { request_id, .. }
+			| VssError::RateLimitedError { request_id, .. }
+			| VssError::TimeoutError { request_id, .. } => *request_id = Some(id.to_string()),
+			_ => {},
+		}
+		self
+	}
+
+	/// Returns the [`ErrorCode`] corresponding to the error reported by the VSS server, or `None` if
+	/// the error didn't originate from a recognized [`ErrorResponse`].
+	pub fn error_code(&self) -> Option<ErrorCode> {
+		match self {
+			VssError::NoSuchKeyError { .. } => Some(ErrorCode::NoSuchKeyException),
+			VssError::InvalidRequestError(..) => Some(ErrorCode::InvalidRequestException),
+			VssError::ConflictError { .. }
+			| VssError::AlreadyExistsError(..)
+			| VssError::CasConflictError(..)
+			| VssError::GlobalVersionConflictError { .. } => Some(ErrorCode::ConflictException),
+			VssError::AuthError(..) => Some(ErrorCode::AuthException),
+			VssError::InternalServerError { .. } => Some(ErrorCode::InternalServerException),
+			VssError::InternalError { .. }
+			| VssError::RateLimitedError { .. }
+			| VssError::TimeoutError { .. }
+			| VssError::InvalidArgumentError(..)
+			| VssError::CircuitOpenError(..)
+			| VssError::TlsConfigurationError(..)
+			| VssError::SerializationError(..)
+			| VssError::ChecksumMismatchError(..)
+			| VssError::ChunkedValueError(..)
+			| VssError::DecryptionError(..) => None,
+		}
+	}
+
+	/// Returns `true` if the error is transient and the request may succeed when retried, i.e. for
+	/// server-side errors, network errors, timeouts and rate limiting, or `false` if it is
+	/// permanent, e.g. for conflicts, missing keys, rejected credentials and invalid requests.
+	///
+	/// A [`VssError::InternalError`] is only retryable if it resulted from a `5xx` response, or from
+	/// a network error without any response, e.g. a refused connection. Any other one, e.g. an
+	/// undecodable `4xx` response from a misconfigured base URL, won't resolve on its own.
+	///
+	/// Errors for which this returns `false` aren't retried by [`ExponentialBackoffRetryPolicy`],
+	/// nor counted as failures by circuit breakers.
+	///
+	/// [`ExponentialBackoffRetryPolicy`]: crate::util::retry::ExponentialBackoffRetryPolicy
+	pub fn is_transient(&self) -> bool {
+		match self {
+			VssError::InternalServerError { .. }
+			| VssError::TimeoutError { .. }
+			| VssError::RateLimitedError { .. } => true,
+			VssError::InternalError { status: Some(status), .. } => (500..600).contains(status),
+			VssError::InternalError { status: None, .. } => {
+				self.transport_error().map_or(false, |e| {
+					e.is_connect() || e.is_request() || e.is_timeout() || e.is_body()
+				})
+			},
+			VssError::NoSuchKeyError { .. }
+			| VssError::InvalidRequestError(..)
+			| VssError::ConflictError { .. }
+			| VssError::AlreadyExistsError(..)
+			| VssError::CasConflictError(..)
+			| VssError::GlobalVersionConflictError { .. }
+			| VssError::AuthError(..)
+			| VssError::InvalidArgumentError(..)
+			| VssError::CircuitOpenError(..)
+			| VssError::TlsConfigurationError(..)
+			| VssError::SerializationError(..)
+			| VssError::ChecksumMismatchError(..)
+			| VssError::ChunkedValueError(..)
+			| VssError::DecryptionError(..) => false,
+		}
+	}
+
+	/// Returns `true` if the request may succeed when retried, same as [`Self::is_transient`].
+	pub fn is_retryable(&self) -> bool {
+		self.is_transient()
+	}
+
+	// Returns whether the request failed without reaching the server, or without a response from
+	// it, such that a write may be safely replayed later.
+	pub(crate) fn is_transport_failure(&self) -> bool {
+		match self {
+			VssError::TimeoutError { .. } => true,
+			_ => self
+				.transport_error()
+				.map_or(false, |e| e.is_connect() || e.is_request() || e.is_timeout()),
+		}
+	}
+
+	// Returns the underlying `reqwest::Error` of an `InternalError` not created from a response.
+	fn transport_error(&self) -> Option<&reqwest::Error> {
+		match self {
+			VssError::InternalError { source: Some(source), status: None, .. } => {
+				source.downcast_ref::<reqwest::Error>()
+			},
+			_ => None,
+		}
+	}
+
+	// Returns `true` if the error indicates that the VSS server is failing or unreachable, as
+	// counted by circuit breakers. Unlike `is_transient`, rate limiting is excluded, as the server
+	// is available, but asks the client to back off.
+	pub(crate) fn is_server_failure(&self) -> bool {
+		self.is_transient() && !matches!(self, VssError::RateLimitedError { .. })
+	}
+
+	/// Returns the key the error is about, i.e. the missing key of a [`VssError::NoSuchKeyError`] or
+	/// the conflicting key of a [`VssError::ConflictError`], if known.
+	pub fn key(&self) -> Option<&str> {
+		match self {
+			VssError::NoSuchKeyError { key, .. } | VssError::ConflictError { key, .. } => {
+				key.as_deref()
 			},
+			_ => None,
+		}
+	}
+
+	/// Returns `true` if the requested key doesn't exist, see [`VssError::NoSuchKeyError`].
+	pub fn is_not_found(&self) -> bool {
+		matches!(self, VssError::NoSuchKeyError { .. })
+	}
+
+	/// Returns `true` if a write failed due to a version mismatch, see [`VssError::ConflictError`].
+	pub fn is_conflict(&self) -> bool {
+		matches!(self, VssError::ConflictError { .. })
+	}
+
+	/// Returns the HTTP status code used by the VSS server for this kind of error, or `None` if
+	/// the error didn't originate from the server, e.g. a network error.
+	///
+	/// For the actual status of the response, e.g. to tell a `503` from a `500`, both resulting in
+	/// a [`VssError::InternalServerError`], refer to [`Self::http_status`].
+	pub fn status_code(&self) -> Option<u16> {
+		match self {
+			VssError::NoSuchKeyError { .. } => Some(404),
+			VssError::InvalidRequestError(..) => Some(400),
+			VssError::ConflictError { .. }
+			| VssError::AlreadyExistsError(..)
+			| VssError::CasConflictError(..)
+			| VssError::GlobalVersionConflictError { .. } => Some(409),
+			VssError::AuthError(..) => Some(401),
+			VssError::InternalServerError { .. } => Some(500),
+			VssError::RateLimitedError { .. } => Some(429),
+			VssError::InternalError { .. }
+			| VssError::TimeoutError { .. }
+			| VssError::InvalidArgumentError(..)
+			| VssError::CircuitOpenError(..)
+			| VssError::TlsConfigurationError(..)
+			| VssError::SerializationError(..)
+			| VssError::ChecksumMismatchError(..)
+			| VssError::ChunkedValueError(..)
+			| VssError::DecryptionError(..) => None,
 		}
 	}
+
+	/// Returns the HTTP status of the response the error was created from, if known.
+	///
+	/// Unlike [`Self::status_code`], this is the status actually returned, e.g. by the VSS server
+	/// or a proxy in front of it. It is known for [`VssError::InternalServerError`],
+	/// [`VssError::InternalError`] and [`VssError::RateLimitedError`], which may result from
+	/// various statuses, and `None` for client-side errors or any other variant, for which the
+	/// status given by [`Self::status_code`] applies.
+	pub fn http_status(&self) -> Option<u16> {
+		match self {
+			VssError::InternalServerError { status, .. }
+			| VssError::InternalError { status, .. }
+			| VssError::RateLimitedError { status, .. } => *status,
+			_ => None,
+		}
+	}
+
+	/// Returns the delay the server asked the client to wait for before retrying, as given by the
+	/// `Retry-After` header of a [`VssError::RateLimitedError`].
+	pub fn retry_after(&self) -> Option<Duration> {
+		match self {
+			VssError::RateLimitedError { retry_after, .. } => *retry_after,
+			_ => None,
+		}
+	}
+
+	/// Returns the id of the request the error resulted from, if any, to correlate it with the logs
+	/// of the VSS server, see [`RequestOptions::request_id`]. It's also included in the error's
+	/// [`Display`] output.
+	///
+	/// It is known for the variants carrying a `request_id` field, i.e. the errors of a request
+	/// reaching the server or failing in transport, except for [`VssError::InvalidRequestError`]
+	/// and [`VssError::AuthError`], and `None` for client-side errors.
+	///
+	/// [`RequestOptions::request_id`]: crate::client::RequestOptions::request_id
+	pub fn request_id(&self) -> Option<&str> {
+		match self {
+			VssError::NoSuchKeyError { request_id, .. }
+			| VssError::ConflictError { request_id, .. }
+			| VssError::GlobalVersionConflictError { request_id, .. }
+			| VssError::InternalServerError { request_id, .. }
+			| VssError::InternalError { request_id, .. }
+			| VssError::RateLimitedError { request_id, .. }
+			| VssError::TimeoutError { request_id, .. } => request_id.as_deref(),
+			_ => None,
+		}
+	}
+
+	/// Create new instance of `VssError` from an HTTP response, additionally considering its
+	/// `headers`.
+	pub(crate) fn from_response(
+		status: StatusCode, headers: &HeaderMap, payload: Bytes,
+	) -> VssError {
+		let retry_after = parse_retry_after(headers);
+		if status == StatusCode::TOO_MANY_REQUESTS
+			|| (status == StatusCode::SERVICE_UNAVAILABLE && retry_after.is_some())
+		{
+			let message = match ErrorResponse::decode(&payload[..]) {
+				Ok(error_response) => error_response.message,
+				Err(_) => format!("HttpStatusCode: {}", status),
+			};
+			VssError::RateLimitedError {
+				message,
+				retry_after,
+				status: Some(status.as_u16()),
+				request_id: None,
+			}
+		} else {
+			VssError::new(status, payload)
+		}
+	}
+}
+
+// Parses the `Retry-After` header, given either as a number of seconds or as an HTTP-date.
+fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+	let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+	if let Ok(secs) = value.parse::<u64>() {
+		return Some(Duration::from_secs(secs));
+	}
+	let retry_at = httpdate::parse_http_date(value).ok()?;
+	Some(retry_at.duration_since(SystemTime::now()).unwrap_or(Duration::ZERO))
+}
+
+// The maximum number of bytes of an undecodable response body included in error messages.
+const MAX_BODY_SNIPPET_LEN: usize = 256;
+
+// Returns the (possibly truncated) response body as text, to be included in error messages.
+fn body_snippet(payload: &[u8]) -> String {
+	if payload.len() > MAX_BODY_SNIPPET_LEN {
+		format!("{}...", String::from_utf8_lossy(&payload[..MAX_BODY_SNIPPET_LEN]))
+	} else {
+		String::from_utf8_lossy(payload).into_owned()
+	}
+}
+
+// Returns whether the server, or a proxy in front of it, rejected the credentials of a request.
+pub(crate) fn is_auth_failure(status: StatusCode) -> bool {
+	status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN
+}
+
+fn is_transient_server_error(status: StatusCode) -> bool {
+	matches!(
+		status,
+		StatusCode::INTERNAL_SERVER_ERROR
+			| StatusCode::BAD_GATEWAY
+			| StatusCode::SERVICE_UNAVAILABLE
+			| StatusCode::GATEWAY_TIMEOUT
+	)
 }
 
 impl Display for VssError {
 	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
 		match self {
-			VssError::NoSuchKeyError(message) => {
-				write!(f, "Requested key does not exist: {}", message)
+			VssError::NoSuchKeyError { message, store_id, key, .. } => match (key, store_id) {
+				(Some(key), Some(store_id)) => write!(
+					f,
+					"Requested key: {} does not exist in store: {}: {}",
+					key, store_id, message
+				),
+				(Some(key), None) => {
+					write!(f, "Requested key: {} does not exist: {}", key, message)
+				},
+				_ => write!(f, "Requested key does not exist: {}", message),
 			},
 			VssError::InvalidRequestError(message) => {
 				write!(f, "Request sent to VSS Storage was invalid: {}", message)
 			},
-			VssError::ConflictError(message) => {
+			VssError::ConflictError { message, .. } => {
 				write!(f, "Potential version conflict in write operation: {}", message)
 			},
+			VssError::AlreadyExistsError(message) => {
+				write!(f, "Key already exists: {}", message)
+			},
+			VssError::CasConflictError(message) => {
+				write!(f, "Compare-and-swap conflict: {}", message)
+			},
+			VssError::GlobalVersionConflictError {
+				message,
+				expected_global_version,
+				current_global_version,
+				..
+			} => {
+				write!(
+					f,
+					"Global version conflict in write operation, expected: {}, found: {}: {}",
+					expected_global_version, current_global_version, message
+				)
+			},
 			VssError::AuthError(message) => {
 				write!(f, "Authentication or Authorization failure: {}", message)
 			},
-			VssError::InternalServerError(message) => {
+			VssError::InternalServerError { message, .. } => {
 				write!(f, "InternalServerError: {}", message)
 			},
-			VssError::InternalError(message) => {
+			VssError::InternalError { message, .. } => {
 				write!(f, "InternalError: {}", message)
 			},
+			VssError::RateLimitedError { message, .. } => {
+				write!(f, "Rate limited by VSS server: {}", message)
+			},
+			VssError::TimeoutError { message, .. } => {
+				write!(f, "Request timed out: {}", message)
+			},
+			VssError::InvalidArgumentError(message) => {
+				write!(f, "Invalid argument: {}", message)
+			},
+			VssError::CircuitOpenError(message) => {
+				write!(f, "Circuit breaker is open: {}", message)
+			},
+			VssError::TlsConfigurationError(message) => {
+				write!(f, "Invalid TLS configuration: {}", message)
+			},
+			VssError::SerializationError(message) => {
+				write!(f, "Serialization failure: {}", message)
+			},
+			VssError::ChecksumMismatchError(message) => {
+				write!(f, "Checksum verification failed: {}", message)
+			},
+			VssError::ChunkedValueError(message) => {
+				write!(f, "Invalid chunked value: {}", message)
+			},
+			VssError::DecryptionError(message) => {
+				write!(f, "Failed to decrypt value: {}", message)
+			},
+		}?;
+		if let Some(request_id) = self.request_id() {
+			write!(f, " (request id: {})", request_id)?;
 		}
+		Ok(())
 	}
 }
 
-impl Error for VssError {}
+impl Error for VssError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			VssError::InternalError { source, .. } | VssError::TimeoutError { source, .. } => {
+				source.as_ref().map(|source| source.as_ref() as &(dyn Error + 'static))
+			},
+			_ => None,
+		}
+	}
+}
 
 impl From<ErrorResponse> for VssError {
 	fn from(error_response: ErrorResponse) -> Self {
 		match error_response.error_code() {
-			ErrorCode::NoSuchKeyException => VssError::NoSuchKeyError(error_response.message),
+			ErrorCode::NoSuchKeyException => VssError::NoSuchKeyError {
+				message: error_response.message,
+				store_id: None,
+				key: None,
+				request_id: None,
+			},
 			ErrorCode::InvalidRequestException => {
 				VssError::InvalidRequestError(error_response.message)
 			},
-			ErrorCode::ConflictException => VssError::ConflictError(error_response.message),
+			ErrorCode::ConflictException => VssError::ConflictError {
+				message: error_response.message,
+				key: None,
+				expected_version: None,
+				current_version: None,
+				request_id: None,
+			},
 			ErrorCode::AuthException => VssError::AuthError(error_response.message),
-			ErrorCode::InternalServerException => {
-				VssError::InternalServerError(error_response.message)
+			ErrorCode::InternalServerException => VssError::InternalServerError {
+				message: error_response.message,
+				status: None,
+				request_id: None,
+			},
+			_ => VssError::InternalError {
+				message: format!(
+					"VSS responded with an unknown error code: {}, message: {}",
+					error_response.error_code, error_response.message
+				),
+				source: None,
+				status: None,
+				request_id: None,
 			},
-			_ => VssError::InternalError(format!(
-				"VSS responded with an unknown error code: {}, message: {}",
-				error_response.error_code, error_response.message
-			)),
 		}
 	}
 }
 
 impl From<DecodeError> for VssError {
 	fn from(err: DecodeError) -> Self {
-		VssError::InternalError(err.to_string())
+		VssError::InternalError {
+			message: err.to_string(),
+			source: Some(Box::new(err)),
+			status: None,
+			request_id: None,
+		}
 	}
 }
 
 impl From<reqwest::Error> for VssError {
 	fn from(err: reqwest::Error) -> Self {
-		VssError::InternalError(err.to_string())
+		let message = err.to_string();
+		if err.is_timeout() {
+			VssError::TimeoutError { message, source: Some(Box::new(err)), request_id: None }
+		} else {
+			VssError::InternalError {
+				message,
+				source: Some(Box::new(err)),
+				status: None,
+				request_id: None,
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use reqwest::header::HeaderValue;
+
+	fn headers(retry_after: &str) -> HeaderMap {
+		let mut headers = HeaderMap::new();
+		headers.insert(RETRY_AFTER, HeaderValue::from_str(retry_after).unwrap());
+		headers
+	}
+
+	#[test]
+	fn classifies_errors() {
+		let message = || "message".to_string();
+		let conflict = VssError::ConflictError {
+			message: message(),
+			key: None,
+			expected_version: None,
+			current_version: None,
+			request_id: None,
+		};
+		let global_version_conflict = VssError::GlobalVersionConflictError {
+			message: message(),
+			expected_global_version: 1,
+			current_global_version: 2,
+			request_id: None,
+		};
+		let rate_limited = VssError::RateLimitedError {
+			message: message(),
+			retry_after: None,
+			status: None,
+			request_id: None,
+		};
+		let no_such_key = VssError::NoSuchKeyError {
+			message: message(),
+			store_id: None,
+			key: None,
+			request_id: None,
+		};
+		// (error, is_transient, is_not_found, is_conflict, status_code)
+		let cases = vec![
+			(no_such_key, false, true, false, Some(404)),
+			(VssError::InvalidRequestError(message()), false, false, false, Some(400)),
+			(conflict, false, false, true, Some(409)),
+			(VssError::AlreadyExistsError(message()), false, false, false, Some(409)),
+			(VssError::CasConflictError(message()), false, false, false, Some(409)),
+			(global_version_conflict, false, false, false, Some(409)),
+			(VssError::AuthError(message()), false, false, false, Some(401)),
+			(
+				VssError::InternalServerError {
+					message: message(),
+					status: None,
+					request_id: None,
+				},
+				true,
+				false,
+				false,
+				Some(500),
+			),
+			(
+				VssError::InternalError {
+					message: message(),
+					source: None,
+					status: None,
+					request_id: None,
+				},
+				false,
+				false,
+				false,
+				None,
+			),
+			(
+				VssError::InternalError {
+					message: message(),
+					source: None,
+					status: Some(501),
+					request_id: None,
+				},
+				true,
+				false,
+				false,
+				None,
+			),
+			(rate_limited, true, false, false, Some(429)),
+			(
+				VssError::TimeoutError { message: message(), source: None, request_id: None },
+				true,
+				false,
+				false,
+				None,
+			),
+			(VssError::InvalidArgumentError(message()), false, false, false, None),
+			(VssError::CircuitOpenError(message()), false, false, false, None),
+			(VssError::TlsConfigurationError(message()), false, false, false, None),
+			(VssError::SerializationError(message()), false, false, false, None),
+			(VssError::ChecksumMismatchError(message()), false, false, false, None),
+			(VssError::ChunkedValueError(message()), false, false, false, None),
+			(VssError::DecryptionError(message()), false, false, false, None),
+		];
+		for (error, is_transient, is_not_found, is_conflict, status_code) in cases {
+			assert_eq!(error.is_transient(), is_transient, "{}", error);
+			assert_eq!(error.is_retryable(), is_transient, "{}", error);
+			assert_eq!(error.is_not_found(), is_not_found, "{}", error);
+			assert_eq!(error.is_conflict(), is_conflict, "{}", error);
+			assert_eq!(error.status_code(), status_code, "{}", error);
+		}
+
+		// Neither undecodable responses, e.g. from a misconfigured base URL, nor other failures
+		// unrelated to the network resolve on their own.
+		let error = VssError::new(StatusCode::NOT_FOUND, Bytes::from_static(b"<html>404</html>"));
+		assert!(!error.is_retryable(), "{}", error);
+		let error = VssError::from(ErrorResponse::decode(&[0xff][..]).unwrap_err());
+		assert!(!error.is_retryable(), "{}", error);
+		let error_response = ErrorResponse { error_code: 42, message: message() };
+		let error = VssError::new(
+			StatusCode::from_u16(999).unwrap(),
+			error_response.encode_to_vec().into(),
+		);
+		assert!(!error.is_retryable(), "{}", error);
+	}
+
+	#[test]
+	fn decodes_error_response() {
+		let error_response = ErrorResponse {
+			error_code: ErrorCode::ConflictException.into(),
+			message: "Version mismatch".to_string(),
+		};
+		let error = VssError::new(StatusCode::CONFLICT, error_response.encode_to_vec().into());
+		assert!(matches!(
+			&error,
+			VssError::ConflictError { message, key: None, .. } if message == "Version mismatch"
+		));
+		// The message is displayed as before.
+		assert_eq!(
+			error.to_string(),
+			"Potential version conflict in write operation: Version mismatch"
+		);
+		assert_eq!(error.error_code(), Some(ErrorCode::ConflictException));
+
+		// Undecodable bodies are included in the error message.
+		let error = VssError::new(StatusCode::NOT_FOUND, Bytes::from_static(b"<html>404</html>"));
+		assert!(
+			matches!(&error, VssError::InternalError { message, .. } if message.contains("<html>404</html>"))
+		);
+		assert_eq!(error.error_code(), None);
+
+		// Empty bodies decode into an empty `ErrorResponse`, which isn't treated as a valid one.
+		let error = VssError::new(StatusCode::BAD_REQUEST, Bytes::new());
+		assert!(
+			matches!(&error, VssError::InternalError { message, .. } if message.contains("Empty ErrorResponse"))
+		);
+
+		// Long bodies are truncated.
+		let error = VssError::new(StatusCode::BAD_REQUEST, Bytes::from(vec![b'a'; 1000]));
+		let message = error.to_string();
+		assert!(message.ends_with("...") && message.len() < 1000);
+	}
+
+	#[test]
+	fn maps_error_codes_of_error_responses() {
+		let cases = [
+			(ErrorCode::NoSuchKeyException, StatusCode::NOT_FOUND),
+			(ErrorCode::InvalidRequestException, StatusCode::BAD_REQUEST),
+			(ErrorCode::ConflictException, StatusCode::CONFLICT),
+			(ErrorCode::AuthException, StatusCode::UNAUTHORIZED),
+			(ErrorCode::InternalServerException, StatusCode::INTERNAL_SERVER_ERROR),
+		];
+		for (error_code, status) in cases {
+			let error_response =
+				ErrorResponse { error_code: error_code.into(), message: "message".to_string() };
+			let error = VssError::new(status, error_response.encode_to_vec().into());
+			assert_eq!(error.error_code(), Some(error_code), "{}", error);
+			assert_eq!(error.status_code(), Some(status.as_u16()), "{}", error);
+			assert!(error.to_string().ends_with(": message"), "{}", error);
+		}
+
+		// Unknown error codes are reported along with their message.
+		let error_response = ErrorResponse { error_code: 42, message: "message".to_string() };
+		let error = VssError::new(StatusCode::BAD_REQUEST, error_response.encode_to_vec().into());
+		assert!(matches!(error, VssError::InternalError { .. }), "{}", error);
+		assert!(error.to_string().contains("unknown error code: 42, message: message"));
+	}
+
+	#[test]
+	fn maps_undecodable_error_responses_by_status() {
+		let garbage = Bytes::from_static(b"\xff\xffnot a protobuf");
+		for status in [
+			StatusCode::BAD_REQUEST,
+			StatusCode::NOT_FOUND,
+			StatusCode::CONFLICT,
+			StatusCode::NOT_IMPLEMENTED,
+		] {
+			let error = VssError::new(status, garbage.clone());
+			assert!(matches!(error, VssError::InternalError { .. }), "{}", error);
+			assert_eq!(error.error_code(), None);
+			let message = error.to_string();
+			assert!(message.contains(&status.to_string()) && message.contains("not a protobuf"));
+		}
+		// Rejected credentials aren't retried, but need to be fixed by the application.
+		for status in [StatusCode::UNAUTHORIZED, StatusCode::FORBIDDEN] {
+			let error = VssError::new(status, garbage.clone());
+			assert!(matches!(error, VssError::AuthError(..)), "{}", error);
+			assert!(!error.is_retryable());
+			assert!(error.to_string().contains(&status.to_string()));
+			let error = VssError::new(status, Bytes::new());
+			assert!(matches!(error, VssError::AuthError(..)), "{}", error);
+			let unknown_error_code =
+				ErrorResponse { error_code: 42, message: "message".to_string() };
+			let error = VssError::new(status, unknown_error_code.encode_to_vec().into());
+			assert!(matches!(error, VssError::AuthError(..)), "{}", error);
+		}
+		// Transient server-side failures remain retryable.
+		for status in [
+			StatusCode::INTERNAL_SERVER_ERROR,
+			StatusCode::BAD_GATEWAY,
+			StatusCode::SERVICE_UNAVAILABLE,
+			StatusCode::GATEWAY_TIMEOUT,
+		] {
+			let error = VssError::new(status, garbage.clone());
+			assert!(matches!(error, VssError::InternalServerError { .. }), "{}", error);
+			assert!(error.is_retryable());
+			let message = error.to_string();
+			assert!(message.contains(&status.to_string()) && message.contains("not a protobuf"));
+		}
+	}
+
+	#[test]
+	fn exposes_http_status() {
+		let internal_server_error = ErrorResponse {
+			error_code: ErrorCode::InternalServerException.into(),
+			message: "message".to_string(),
+		};
+		let error =
+			VssError::new(StatusCode::BAD_GATEWAY, internal_server_error.encode_to_vec().into());
+		assert!(matches!(error, VssError::InternalServerError { .. }));
+		assert_eq!(error.http_status(), Some(502));
+		assert_eq!(error.status_code(), Some(500));
+
+		let error = VssError::new(StatusCode::SERVICE_UNAVAILABLE, Bytes::new());
+		assert!(matches!(error, VssError::InternalServerError { .. }));
+		assert_eq!(error.http_status(), Some(503));
+
+		let error = VssError::new(StatusCode::NOT_IMPLEMENTED, Bytes::new());
+		assert!(matches!(error, VssError::InternalError { .. }));
+		assert_eq!(error.http_status(), Some(501));
+
+		let headers = headers("5");
+		for status in [StatusCode::TOO_MANY_REQUESTS, StatusCode::SERVICE_UNAVAILABLE] {
+			let error = VssError::from_response(status, &headers, Bytes::new());
+			assert!(matches!(error, VssError::RateLimitedError { .. }));
+			assert_eq!(error.http_status(), Some(status.as_u16()));
+		}
+
+		// Errors with a fixed status or originating from the client don't carry one.
+		let no_such_key = ErrorResponse {
+			error_code: ErrorCode::NoSuchKeyException.into(),
+			message: "message".to_string(),
+		};
+		let error = VssError::new(StatusCode::NOT_FOUND, no_such_key.encode_to_vec().into());
+		assert_eq!(error.http_status(), None);
+		let error = VssError::from(ErrorResponse::decode(&[0xff][..]).unwrap_err());
+		assert_eq!(error.http_status(), None);
+
+		// The status isn't displayed, which remains as before.
+		let error =
+			VssError::new(StatusCode::BAD_GATEWAY, internal_server_error.encode_to_vec().into());
+		assert_eq!(error.to_string(), "InternalServerError: message");
+	}
+
+	#[test]
+	fn exposes_source_of_wrapped_errors() {
+		let decode_error = ErrorResponse::decode(&[0xff][..]).unwrap_err();
+		let decode_error_message = decode_error.to_string();
+		let error = VssError::from(decode_error);
+		let source = error.source().unwrap();
+		assert!(source.is::<DecodeError>());
+		assert_eq!(source.to_string(), decode_error_message);
+
+		let error = VssError::InvalidRequestError("Invalid request".to_string());
+		assert!(error.source().is_none());
+	}
+
+	#[test]
+	fn parses_retry_after_seconds() {
+		assert_eq!(parse_retry_after(&headers("120")), Some(Duration::from_secs(120)));
+		assert_eq!(parse_retry_after(&headers("invalid")), None);
+		assert_eq!(parse_retry_after(&HeaderMap::new()), None);
+	}
+
+	#[test]
+	fn parses_retry_after_http_date() {
+		let retry_at = SystemTime::now() + Duration::from_secs(120);
+		let retry_after = parse_retry_after(&headers(&httpdate::fmt_http_date(retry_at))).unwrap();
+		// HTTP-dates have a resolution of seconds.
+		assert!(retry_after > Duration::from_secs(115) && retry_after <= Duration::from_secs(120));
+
+		// Dates in the past result in retrying immediately.
+		assert_eq!(
+			parse_retry_after(&headers("Wed, 21 Oct 2015 07:28:00 GMT")),
+			Some(Duration::ZERO)
+		);
+	}
+
+	#[test]
+	fn maps_rate_limited_responses() {
+		let error =
+			VssError::from_response(StatusCode::TOO_MANY_REQUESTS, &HeaderMap::new(), Bytes::new());
+		assert!(matches!(error, VssError::RateLimitedError { retry_after: None, .. }));
+
+		let error =
+			VssError::from_response(StatusCode::SERVICE_UNAVAILABLE, &headers("5"), Bytes::new());
+		assert!(matches!(error, VssError::RateLimitedError { .. }));
+		assert_eq!(error.retry_after(), Some(Duration::from_secs(5)));
+
+		let error = VssError::from_response(
+			StatusCode::SERVICE_UNAVAILABLE,
+			&HeaderMap::new(),
+			Bytes::new(),
+		);
+		assert!(matches!(error, VssError::InternalServerError { .. }));
 	}
 }
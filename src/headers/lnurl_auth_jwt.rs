@@ -1,3 +1,4 @@
+use crate::client::DEFAULT_USER_AGENT;
 use crate::headers::{get_headermap, VssHeaderProvider, VssHeaderProviderError};
 use async_trait::async_trait;
 use base64::engine::general_purpose::URL_SAFE_NO_PAD;
@@ -8,6 +9,7 @@ use bitcoin::hashes::sha256;
 use bitcoin::hashes::{Hash, HashEngine, Hmac, HmacEngine};
 use bitcoin::secp256k1::{Message, Secp256k1, SignOnly};
 use bitcoin::PrivateKey;
+use reqwest::header::HeaderMap;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::sync::RwLock;
@@ -51,6 +53,7 @@ pub struct LnurlAuthToJwtProvider {
 	parent_key: Xpriv,
 	url: String,
 	default_headers: HashMap<String, String>,
+	default_headermap: HeaderMap,
 	client: reqwest::Client,
 	cached_jwt_token: RwLock<Option<JwtToken>>,
 }
@@ -71,18 +74,28 @@ impl LnurlAuthToJwtProvider {
 	pub fn new(
 		parent_key: Xpriv, url: String, default_headers: HashMap<String, String>,
 	) -> Result<LnurlAuthToJwtProvider, VssHeaderProviderError> {
-		let engine = Secp256k1::signing_only();
-		let default_headermap = get_headermap(&default_headers)?;
 		let client = reqwest::Client::builder()
-			.default_headers(default_headermap)
+			.user_agent(DEFAULT_USER_AGENT)
 			.build()
 			.map_err(VssHeaderProviderError::from)?;
+		Self::from_client(parent_key, url, default_headers, client)
+	}
+
+	/// Like [`Self::new`], but makes the LNURL requests via the given [`reqwest::Client`], e.g. to
+	/// route them through the same proxy as the requests to VSS.
+	pub fn from_client(
+		parent_key: Xpriv, url: String, default_headers: HashMap<String, String>,
+		client: reqwest::Client,
+	) -> Result<LnurlAuthToJwtProvider, VssHeaderProviderError> {
+		let engine = Secp256k1::signing_only();
+		let default_headermap = get_headermap(&default_headers)?;
 
 		Ok(LnurlAuthToJwtProvider {
 			engine,
 			parent_key,
 			url,
 			default_headers,
+			default_headermap,
 			client,
 			cached_jwt_token: RwLock::new(None),
 		})
@@ -90,27 +103,39 @@ impl LnurlAuthToJwtProvider {
 
 	async fn fetch_jwt_token(&self) -> Result<JwtToken, VssHeaderProviderError> {
 		// Fetch the LNURL.
-		let lnurl_str = self
+		let lnurl_response = self
 			.client
 			.get(&self.url)
+			.headers(self.default_headermap.clone())
 			.send()
 			.await
-			.map_err(VssHeaderProviderError::from)?
-			.text()
-			.await
 			.map_err(VssHeaderProviderError::from)?;
+		if !lnurl_response.status().is_success() {
+			return Err(VssHeaderProviderError::RequestError {
+				error: format!("LNURL request failed with status: {}", lnurl_response.status()),
+			});
+		}
+		let lnurl_str = lnurl_response.text().await.map_err(VssHeaderProviderError::from)?;
 
 		// Sign the LNURL and perform the request.
 		let signed_lnurl = sign_lnurl(&self.engine, &self.parent_key, &lnurl_str)?;
-		let lnurl_auth_response: LnurlAuthResponse = self
+		let callback_response = self
 			.client
 			.get(&signed_lnurl)
+			.headers(self.default_headermap.clone())
 			.send()
 			.await
-			.map_err(VssHeaderProviderError::from)?
-			.json()
-			.await
 			.map_err(VssHeaderProviderError::from)?;
+		if !callback_response.status().is_success() {
+			return Err(VssHeaderProviderError::RequestError {
+				error: format!(
+					"LNURL Auth callback failed with status: {}",
+					callback_response.status()
+				),
+			});
+		}
+		let lnurl_auth_response: LnurlAuthResponse =
+			callback_response.json().await.map_err(VssHeaderProviderError::from)?;
 
 		let untrusted_token = match lnurl_auth_response {
 			LnurlAuthResponse { token: Some(token), .. } => token,
@@ -155,6 +180,10 @@ impl VssHeaderProvider for LnurlAuthToJwtProvider {
 		headers.insert(AUTHORIZATION.to_string(), format!("Bearer {}", jwt_token));
 		Ok(headers)
 	}
+
+	async fn on_unauthorized(&self) {
+		*self.cached_jwt_token.write().unwrap() = None;
+	}
 }
 
 fn hashing_key(
@@ -264,15 +293,60 @@ impl From<reqwest::Error> for VssHeaderProviderError {
 
 #[cfg(test)]
 mod test {
-	use crate::headers::lnurl_auth_jwt::{linking_key_path, sign_lnurl};
+	use crate::headers::lnurl_auth_jwt::{linking_key_path, sign_lnurl, LnurlAuthToJwtProvider};
+	use crate::headers::{VssHeaderProvider, VssHeaderProviderError};
+	use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+	use base64::Engine;
 	use bitcoin::bip32::Xpriv;
 	use bitcoin::hashes::hex::FromHex;
 	use bitcoin::secp256k1::Secp256k1;
 	use bitcoin::secp256k1::SecretKey;
 	use bitcoin::Network;
 	use bitcoin::PrivateKey;
+	use mockito::Matcher;
+	use std::collections::HashMap;
 	use std::str::FromStr;
 
+	const K1: &str = "e2af6254a8df433264fa23f67eb8188635d15ce883e8fc020989d5f82ae6f11e";
+
+	fn parent_key() -> Xpriv {
+		let parent_key_bytes: [u8; 32] =
+			FromHex::from_hex("abababababababababababababababababababababababababababababababab")
+				.unwrap();
+		Xpriv::new_master(Network::Testnet, &parent_key_bytes).unwrap()
+	}
+
+	// LNURL-auth requires a domain name, so the mock server is addressed via `localhost`.
+	fn mock_server_url() -> String {
+		format!("http://localhost:{}", mockito::server_address().port())
+	}
+
+	fn jwt_token() -> String {
+		let encode = |s: &str| URL_SAFE_NO_PAD.encode(s);
+		format!("{}.{}.{}", encode(r#"{"alg":"ES256"}"#), encode("{}"), encode("signature"))
+	}
+
+	fn provider() -> LnurlAuthToJwtProvider {
+		let url = format!("{}/auth", mock_server_url());
+		LnurlAuthToJwtProvider::new(parent_key(), url, HashMap::new()).unwrap()
+	}
+
+	fn mock_lnurl(lnurl: &str) -> mockito::Mock {
+		mockito::mock("GET", "/auth").with_status(200).with_body(lnurl).create()
+	}
+
+	fn mock_callback(status: usize, body: &str) -> mockito::Mock {
+		mockito::mock("GET", Matcher::Regex("^/callback".to_string()))
+			.match_query(Matcher::AllOf(vec![
+				Matcher::UrlEncoded("k1".into(), K1.into()),
+				Matcher::Regex("sig=".into()),
+				Matcher::Regex("key=".into()),
+			]))
+			.with_status(status)
+			.with_body(body)
+			.create()
+	}
+
 	#[test]
 	fn test_linking_key_path() {
 		// Test vector from:
@@ -283,17 +357,14 @@ mod test {
 			Network::Testnet, // The network only matters for serialization.
 		);
 		let path = linking_key_path(&hashing_key, "site.com").unwrap();
-		let numbers: Vec<u32> = path.into_iter().map(|c| u32::from(c.clone())).collect();
+		let numbers: Vec<u32> = path.into_iter().map(|c| u32::from(*c)).collect();
 		assert_eq!(numbers, vec![1588488367, 2659270754, 38110259, 4136336762]);
 	}
 
 	#[test]
 	fn test_sign_lnurl() {
 		let engine = Secp256k1::signing_only();
-		let parent_key_bytes: [u8; 32] =
-			FromHex::from_hex("abababababababababababababababababababababababababababababababab")
-				.unwrap();
-		let parent_key = Xpriv::new_master(Network::Testnet, &parent_key_bytes).unwrap();
+		let parent_key = parent_key();
 		let signed = sign_lnurl(
 			&engine,
 			&parent_key,
@@ -305,4 +376,76 @@ mod test {
 			"https://example.com/path?tag=login&k1=e2af6254a8df433264fa23f67eb8188635d15ce883e8fc020989d5f82ae6f11e&sig=3045022100a75df468de452e618edb8030016eb0894204655c7d93ece1be007fcf36843522022048bc2f00a0a5a30601d274b49cfaf9ef4c76176e5401d0dfb195f5d6ab8ab4c4&key=02d9eb1b467517d685e3b5439082c14bb1a2c9ae672df4d9046d208c193a5846e0",
 		);
 	}
+
+	#[tokio::test]
+	async fn test_fetches_and_caches_jwt_token() {
+		let lnurl_mock = mock_lnurl(&format!("{}/callback?tag=login&k1={}", mock_server_url(), K1));
+		let callback_mock = mock_callback(200, &format!(r#"{{"token":"{}"}}"#, jwt_token()));
+
+		let provider = provider();
+		for _ in 0..2 {
+			let headers = provider.get_headers(&[]).await.unwrap();
+			assert_eq!(headers.get("Authorization"), Some(&format!("Bearer {}", jwt_token())));
+		}
+
+		// The token is cached, so the LNURL-auth flow is only performed once.
+		lnurl_mock.expect(1).assert();
+		callback_mock.expect(1).assert();
+	}
+
+	#[tokio::test]
+	async fn test_refetches_jwt_token_when_unauthorized() {
+		let lnurl_mock = mock_lnurl(&format!("{}/callback?tag=login&k1={}", mock_server_url(), K1));
+		let callback_mock = mock_callback(200, &format!(r#"{{"token":"{}"}}"#, jwt_token()));
+
+		let provider = provider();
+		provider.get_headers(&[]).await.unwrap();
+		provider.on_unauthorized().await;
+		provider.get_headers(&[]).await.unwrap();
+
+		// The cached token was invalidated, so the LNURL-auth flow is performed again.
+		lnurl_mock.expect(2).assert();
+		callback_mock.expect(2).assert();
+	}
+
+	#[tokio::test]
+	async fn test_malformed_lnurl() {
+		let _lnurl_mock = mock_lnurl("not a url");
+
+		let err = provider().get_headers(&[]).await.unwrap_err();
+		assert!(
+			matches!(err, VssHeaderProviderError::InvalidData { ref error } if error.starts_with("invalid lnurl"))
+		);
+	}
+
+	#[tokio::test]
+	async fn test_callback_failure_status() {
+		let _lnurl_mock =
+			mock_lnurl(&format!("{}/callback?tag=login&k1={}", mock_server_url(), K1));
+		let _callback_mock = mock_callback(500, "");
+
+		let err = provider().get_headers(&[]).await.unwrap_err();
+		assert!(
+			matches!(err, VssHeaderProviderError::RequestError { ref error } if error.contains("callback failed with status: 500"))
+		);
+	}
+
+	#[tokio::test]
+	async fn test_callback_response_without_token() {
+		let _lnurl_mock =
+			mock_lnurl(&format!("{}/callback?tag=login&k1={}", mock_server_url(), K1));
+		let _callback_mock = mock_callback(200, r#"{"status":"OK"}"#);
+
+		let err = provider().get_headers(&[]).await.unwrap_err();
+		assert!(
+			matches!(err, VssHeaderProviderError::InvalidData { ref error } if error.contains("did not contain a token"))
+		);
+
+		let _callback_mock =
+			mock_callback(200, r#"{"status":"ERROR","reason":"unknown linking key"}"#);
+		let err = provider().get_headers(&[]).await.unwrap_err();
+		assert!(
+			matches!(err, VssHeaderProviderError::AuthorizationError { ref error } if error.contains("unknown linking key"))
+		);
+	}
 }
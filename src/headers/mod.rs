@@ -23,6 +23,12 @@ pub trait VssHeaderProvider: Send + Sync {
 	async fn get_headers(
 		&self, request: &[u8],
 	) -> Result<HashMap<String, String>, VssHeaderProviderError>;
+
+	/// Invalidates any cached credentials, as the VSS server rejected a request as unauthorized.
+	///
+	/// The rejected request is retried once with freshly provided headers thereafter.
+	/// Does nothing by default.
+	async fn on_unauthorized(&self) {}
 }
 
 /// Errors around providing headers for each VSS request.
@@ -98,9 +104,9 @@ pub(crate) fn get_headermap(
 	let mut headermap = HeaderMap::new();
 	for (name, value) in headers {
 		headermap.insert(
-			reqwest::header::HeaderName::from_str(&name)
+			reqwest::header::HeaderName::from_str(name)
 				.map_err(|e| VssHeaderProviderError::InvalidData { error: e.to_string() })?,
-			reqwest::header::HeaderValue::from_str(&value)
+			reqwest::header::HeaderValue::from_str(value)
 				.map_err(|e| VssHeaderProviderError::InvalidData { error: e.to_string() })?,
 		);
 	}
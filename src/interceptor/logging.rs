@@ -0,0 +1,284 @@
+use async_trait::async_trait;
+use prost::bytes::Bytes;
+use prost::Message;
+use reqwest::StatusCode;
+use std::fmt::Write;
+use std::time::Duration;
+
+use crate::error::VssError;
+use crate::interceptor::Interceptor;
+use crate::types::{
+	DeleteObjectRequest, GetObjectRequest, GetObjectResponse, KeyValue, ListKeyVersionsRequest,
+	ListKeyVersionsResponse, PutObjectRequest,
+};
+
+/// The granularity of what a [`LoggingInterceptor`] logs, each level including the former ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+	/// Nothing is logged.
+	Off,
+	/// The url and size of each request, as well as the status, size and latency of its response,
+	/// are logged at [`log::Level::Info`].
+	Info,
+	/// The store ids, keys and versions of each request and response are logged at
+	/// [`log::Level::Debug`].
+	Debug,
+	/// The encoded payloads of each request and response are logged at [`log::Level::Trace`], if
+	/// values are logged, see [`LoggingInterceptor::log_values`].
+	Trace,
+}
+
+/// An [`Interceptor`] logging the requests made to the VSS server and their responses via the
+/// [`log`] facade.
+///
+/// What is logged is controlled by the [`LogLevel`]. Values are not logged unless enabled via
+/// [`LoggingInterceptor::log_values`], as they may be sensitive.
+pub struct LoggingInterceptor {
+	level: LogLevel,
+	log_values: bool,
+}
+
+impl LoggingInterceptor {
+	/// Constructs a new instance logging at the given `level`, without logging values.
+	pub fn new(level: LogLevel) -> Self {
+		Self { level, log_values: false }
+	}
+
+	/// Sets whether values are logged along with their keys, as well as the encoded payloads
+	/// containing them at [`LogLevel::Trace`].
+	///
+	/// Disabled by default, as values may be sensitive, e.g. if they're not encrypted.
+	pub fn log_values(mut self, log_values: bool) -> Self {
+		self.log_values = log_values;
+		self
+	}
+
+	// Describes the store id, keys and versions of the request to `url`.
+	fn describe_request(&self, url: &str, body: &[u8]) -> Option<String> {
+		let description = match endpoint(url) {
+			"getObject" => {
+				let request = GetObjectRequest::decode(body).ok()?;
+				format!("store_id: {}, key: {}", request.store_id, request.key)
+			},
+			"putObjects" => {
+				let request = PutObjectRequest::decode(body).ok()?;
+				format!(
+					"store_id: {}, global_version: {:?}, transaction_items: [{}], delete_items: [{}]",
+					request.store_id,
+					request.global_version,
+					self.describe_key_values(&request.transaction_items),
+					self.describe_key_values(&request.delete_items)
+				)
+			},
+			"deleteObject" => {
+				let request = DeleteObjectRequest::decode(body).ok()?;
+				let key_value = request.key_value.into_iter().collect::<Vec<_>>();
+				format!(
+					"store_id: {}, key_value: [{}]",
+					request.store_id,
+					self.describe_key_values(&key_value)
+				)
+			},
+			"listKeyVersions" => {
+				let request = ListKeyVersionsRequest::decode(body).ok()?;
+				format!(
+					"store_id: {}, key_prefix: {:?}, page_size: {:?}, page_token: {:?}",
+					request.store_id, request.key_prefix, request.page_size, request.page_token
+				)
+			},
+			_ => return None,
+		};
+		Some(description)
+	}
+
+	// Describes the keys and versions of the successful response of a request to `url`.
+	fn describe_response(&self, url: &str, body: &[u8]) -> Option<String> {
+		let description = match endpoint(url) {
+			"getObject" => {
+				let response = GetObjectResponse::decode(body).ok()?;
+				let value = response.value.into_iter().collect::<Vec<_>>();
+				format!("value: [{}]", self.describe_key_values(&value))
+			},
+			"listKeyVersions" => {
+				let response = ListKeyVersionsResponse::decode(body).ok()?;
+				format!(
+					"key_versions: [{}], global_version: {:?}, next_page_token: {:?}",
+					self.describe_key_values(&response.key_versions),
+					response.global_version,
+					response.next_page_token
+				)
+			},
+			_ => return None,
+		};
+		Some(description)
+	}
+
+	fn describe_key_values(&self, key_values: &[KeyValue]) -> String {
+		let descriptions = key_values.iter().map(|key_value| {
+			if self.log_values {
+				format!("{}@{}: {}", key_value.key, key_value.version, to_hex(&key_value.value))
+			} else {
+				format!("{}@{}", key_value.key, key_value.version)
+			}
+		});
+		descriptions.collect::<Vec<_>>().join(", ")
+	}
+}
+
+#[async_trait]
+impl Interceptor for LoggingInterceptor {
+	async fn before_request(&self, url: &str, body: &mut Bytes) -> Result<(), VssError> {
+		if self.level >= LogLevel::Info {
+			log::info!("Sending VSS request to {}, {} bytes", url, body.len());
+		}
+		if self.level >= LogLevel::Debug {
+			if let Some(description) = self.describe_request(url, body) {
+				log::debug!("VSS request to {}: {}", url, description);
+			}
+		}
+		if self.level >= LogLevel::Trace && self.log_values {
+			log::trace!("VSS request payload to {}: {}", url, to_hex(body));
+		}
+		Ok(())
+	}
+
+	async fn after_response(
+		&self, url: &str, status: StatusCode, latency: Duration, body: &mut Bytes,
+	) -> Result<(), VssError> {
+		if self.level >= LogLevel::Info {
+			log::info!(
+				"Received VSS response from {} with status {}, {} bytes after {:?}",
+				url,
+				status,
+				body.len(),
+				latency
+			);
+		}
+		if self.level >= LogLevel::Debug && status.is_success() {
+			if let Some(description) = self.describe_response(url, body) {
+				log::debug!("VSS response from {}: {}", url, description);
+			}
+		}
+		if self.level >= LogLevel::Trace && self.log_values {
+			log::trace!("VSS response payload from {}: {}", url, to_hex(body));
+		}
+		Ok(())
+	}
+}
+
+fn endpoint(url: &str) -> &str {
+	url.rsplit('/').next().unwrap_or_default()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+	bytes.iter().fold(String::with_capacity(bytes.len() * 2), |mut hex, byte| {
+		// unwrap safety: writing to a `String` can't fail.
+		write!(hex, "{:02x}", byte).unwrap();
+		hex
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::Mutex;
+
+	// Captures the records logged by this module, as the logger can only be set once per process.
+	struct CapturingLogger {
+		records: Mutex<Vec<(log::Level, String)>>,
+	}
+
+	impl log::Log for CapturingLogger {
+		fn enabled(&self, metadata: &log::Metadata) -> bool {
+			metadata.target() == "vss_client::interceptor::logging"
+		}
+
+		fn log(&self, record: &log::Record) {
+			if self.enabled(record.metadata()) {
+				self.records.lock().unwrap().push((record.level(), record.args().to_string()));
+			}
+		}
+
+		fn flush(&self) {}
+	}
+
+	static LOGGER: CapturingLogger = CapturingLogger { records: Mutex::new(Vec::new()) };
+
+	fn put_request_body() -> Bytes {
+		let request = PutObjectRequest {
+			store_id: "store".to_string(),
+			global_version: None,
+			transaction_items: vec![KeyValue {
+				key: "k1".to_string(),
+				version: 2,
+				value: b"secret".to_vec(),
+			}],
+			delete_items: vec![],
+		};
+		request.encode_to_vec().into()
+	}
+
+	async fn intercept(interceptor: &LoggingInterceptor) -> Vec<(log::Level, String)> {
+		LOGGER.records.lock().unwrap().clear();
+		let url = "https://vss.example/vss/putObjects";
+		interceptor.before_request(url, &mut put_request_body()).await.unwrap();
+		let latency = Duration::from_millis(5);
+		interceptor.after_response(url, StatusCode::OK, latency, &mut Bytes::new()).await.unwrap();
+		std::mem::take(&mut *LOGGER.records.lock().unwrap())
+	}
+
+	// All cases are covered by a single test, as the captured records are shared.
+	#[tokio::test]
+	async fn logs_according_to_level() {
+		log::set_logger(&LOGGER).unwrap();
+		log::set_max_level(log::LevelFilter::Trace);
+		let secret_hex = to_hex(b"secret");
+
+		assert!(intercept(&LoggingInterceptor::new(LogLevel::Off)).await.is_empty());
+
+		let records = intercept(&LoggingInterceptor::new(LogLevel::Info)).await;
+		assert_eq!(
+			records,
+			vec![
+				(
+					log::Level::Info,
+					"Sending VSS request to https://vss.example/vss/putObjects, 23 bytes"
+						.to_string()
+				),
+				(
+					log::Level::Info,
+					"Received VSS response from https://vss.example/vss/putObjects with status 200 OK, 0 bytes after 5ms"
+						.to_string()
+				),
+			]
+		);
+
+		// Keys are logged without their values, unless enabled.
+		let records = intercept(&LoggingInterceptor::new(LogLevel::Trace)).await;
+		assert_eq!(records.len(), 3);
+		assert_eq!(
+			records[1],
+			(
+				log::Level::Debug,
+				"VSS request to https://vss.example/vss/putObjects: store_id: store, global_version: None, transaction_items: [k1@2], delete_items: []"
+					.to_string()
+			)
+		);
+		assert!(records.iter().all(|(_, message)| !message.contains(&secret_hex)));
+
+		let interceptor = LoggingInterceptor::new(LogLevel::Trace).log_values(true);
+		let records = intercept(&interceptor).await;
+		assert_eq!(records.len(), 5);
+		assert!(records[1]
+			.1
+			.ends_with(&format!("transaction_items: [k1@2: {}], delete_items: []", secret_hex)));
+		assert_eq!(records[2].0, log::Level::Trace);
+		assert!(records[2].1.ends_with(&to_hex(&put_request_body())));
+	}
+
+	#[test]
+	fn encodes_hex() {
+		assert_eq!(to_hex(&[]), "");
+		assert_eq!(to_hex(&[0x00, 0x0f, 0xab, 0xff]), "000fabff");
+	}
+}
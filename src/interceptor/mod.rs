@@ -0,0 +1,160 @@
+use async_trait::async_trait;
+use prost::bytes::Bytes;
+use reqwest::StatusCode;
+use std::time::Duration;
+
+use crate::error::VssError;
+
+#[cfg(feature = "logging")]
+mod logging;
+
+#[cfg(feature = "logging")]
+pub use logging::{LogLevel, LoggingInterceptor};
+
+/// Intercepts the requests made to the VSS server and their responses, e.g. to log, inspect or
+/// transform their bodies.
+///
+/// Both hooks are called for every attempt of a request, including retried ones. Failing either
+/// of them fails the attempt with the returned error, which is retried according to the retry
+/// policy. Please refer to [`VssClientBuilder::interceptor`] for configuring a [`VssClient`] to use
+/// it, and to [`Interceptor::chain`] for combining multiple interceptors.
+///
+/// [`VssClient`]: crate::client::VssClient
+/// [`VssClientBuilder::interceptor`]: crate::client::VssClientBuilder::interceptor
+#[async_trait]
+pub trait Interceptor: Send + Sync {
+	/// Called before a request is sent to `url`, with its encoded `body`, which may be modified.
+	///
+	/// The request is compressed and signed thereafter, if enabled. Does nothing by default.
+	async fn before_request(&self, _url: &str, _body: &mut Bytes) -> Result<(), VssError> {
+		Ok(())
+	}
+
+	/// Called once a response with the given `status` was received for a request to `url`, with
+	/// its `body`, which may be modified before being decoded.
+	///
+	/// The `latency` is the time since the request was sent, i.e. after [`before_request`] returned.
+	/// The response is decompressed beforehand, if it was compressed. Does nothing by default.
+	///
+	/// [`before_request`]: Interceptor::before_request
+	async fn after_response(
+		&self, _url: &str, _status: StatusCode, _latency: Duration, _body: &mut Bytes,
+	) -> Result<(), VssError> {
+		Ok(())
+	}
+
+	/// Combines this interceptor with `other`, which intercepts requests after this one, and
+	/// responses before this one.
+	fn chain<I: Interceptor>(self, other: I) -> ChainedInterceptor<Self, I>
+	where
+		Self: Sized,
+	{
+		ChainedInterceptor { first: self, second: other }
+	}
+}
+
+/// Two [`Interceptor`]s combined via [`Interceptor::chain`].
+///
+/// Requests pass through `first` before `second`, while responses pass through them in reverse
+/// order. If an interceptor fails, the remaining one isn't called.
+pub struct ChainedInterceptor<I1: Interceptor, I2: Interceptor> {
+	first: I1,
+	second: I2,
+}
+
+#[async_trait]
+impl<I1: Interceptor, I2: Interceptor> Interceptor for ChainedInterceptor<I1, I2> {
+	async fn before_request(&self, url: &str, body: &mut Bytes) -> Result<(), VssError> {
+		self.first.before_request(url, body).await?;
+		self.second.before_request(url, body).await
+	}
+
+	async fn after_response(
+		&self, url: &str, status: StatusCode, latency: Duration, body: &mut Bytes,
+	) -> Result<(), VssError> {
+		self.second.after_response(url, status, latency, body).await?;
+		self.first.after_response(url, status, latency, body).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::{Arc, Mutex};
+
+	// Records the hooks it was called for, appending its `name` to bodies.
+	struct RecordingInterceptor {
+		name: &'static str,
+		calls: Arc<Mutex<Vec<String>>>,
+	}
+
+	#[async_trait]
+	impl Interceptor for RecordingInterceptor {
+		async fn before_request(&self, url: &str, body: &mut Bytes) -> Result<(), VssError> {
+			self.calls.lock().unwrap().push(format!("{} before {}", self.name, url));
+			*body = [&body[..], self.name.as_bytes()].concat().into();
+			Ok(())
+		}
+
+		async fn after_response(
+			&self, url: &str, status: StatusCode, _latency: Duration, body: &mut Bytes,
+		) -> Result<(), VssError> {
+			let call = format!("{} after {} {}", self.name, url, status.as_u16());
+			self.calls.lock().unwrap().push(call);
+			*body = [&body[..], self.name.as_bytes()].concat().into();
+			Ok(())
+		}
+	}
+
+	struct FailingInterceptor;
+
+	#[async_trait]
+	impl Interceptor for FailingInterceptor {
+		async fn before_request(&self, _url: &str, _body: &mut Bytes) -> Result<(), VssError> {
+			Err(VssError::InvalidRequestError("Rejected".to_string()))
+		}
+	}
+
+	#[tokio::test]
+	async fn chains_interceptors() {
+		let calls = Arc::new(Mutex::new(Vec::new()));
+		let interceptor = |name| RecordingInterceptor { name, calls: Arc::clone(&calls) };
+		let chained = interceptor("a").chain(interceptor("b")).chain(interceptor("c"));
+
+		let mut body = Bytes::from_static(b"request-");
+		chained.before_request("url", &mut body).await.unwrap();
+		assert_eq!(&body[..], b"request-abc");
+		let mut body = Bytes::from_static(b"response-");
+		chained.after_response("url", StatusCode::OK, Duration::ZERO, &mut body).await.unwrap();
+		assert_eq!(&body[..], b"response-cba");
+		assert_eq!(
+			*calls.lock().unwrap(),
+			vec![
+				"a before url",
+				"b before url",
+				"c before url",
+				"c after url 200",
+				"b after url 200",
+				"a after url 200"
+			]
+		);
+	}
+
+	#[tokio::test]
+	async fn stops_at_failing_interceptor() {
+		let calls = Arc::new(Mutex::new(Vec::new()));
+		let recording = RecordingInterceptor { name: "a", calls: Arc::clone(&calls) };
+		let chained = FailingInterceptor.chain(recording);
+
+		let mut body = Bytes::from_static(b"request-");
+		let result = chained.before_request("url", &mut body).await;
+		assert!(matches!(result, Err(VssError::InvalidRequestError(..))));
+		assert_eq!(&body[..], b"request-");
+		assert!(calls.lock().unwrap().is_empty());
+
+		// Interceptors not overriding a hook leave it to the others.
+		let mut body = Bytes::from_static(b"response-");
+		chained.after_response("url", StatusCode::OK, Duration::ZERO, &mut body).await.unwrap();
+		assert_eq!(&body[..], b"response-a");
+	}
+}
@@ -0,0 +1,298 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use prost::bytes::Bytes;
+use prost::Message;
+use reqwest::header::{HeaderMap, CONTENT_TYPE};
+use reqwest::StatusCode;
+use serde::de::{self, DeserializeOwned, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+use crate::error::VssError;
+use crate::types::{ErrorCode, ErrorResponse};
+
+pub(crate) const APPLICATION_JSON: &str = "application/json";
+
+/// Defines how requests to and responses from the VSS server are encoded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TransportMode {
+	/// Requests and responses are encoded as protobuf, as expected by the VSS server.
+	#[default]
+	Protobuf,
+	/// Requests are encoded as JSON, following the canonical JSON mapping of protobuf, and sent with
+	/// `Content-Type: application/json`, e.g. for gateways only permitting JSON.
+	///
+	/// Responses are decoded as JSON if their `Content-Type` is `application/json`, and as protobuf
+	/// otherwise.
+	Json,
+}
+
+pub(crate) fn encode_request<Rq: Serialize>(request: &Rq) -> Result<Vec<u8>, VssError> {
+	serde_json::to_vec(request).map_err(|e| VssError::InternalError {
+		message: format!("Failed to encode JSON request: {}", e),
+		source: Some(Box::new(e)),
+		status: None,
+		request_id: None,
+	})
+}
+
+pub(crate) fn is_json_response(headers: &HeaderMap) -> bool {
+	let content_type = headers.get(CONTENT_TYPE).and_then(|value| value.to_str().ok());
+	match content_type {
+		Some(content_type) => {
+			let media_type = content_type.split(';').next().unwrap_or_default().trim();
+			media_type.eq_ignore_ascii_case(APPLICATION_JSON)
+		},
+		None => false,
+	}
+}
+
+pub(crate) fn decode_response<Rs: DeserializeOwned>(
+	status: StatusCode, headers: &HeaderMap, payload: Bytes,
+) -> Result<Rs, VssError> {
+	if status.is_success() {
+		serde_json::from_slice(&payload).map_err(|e| VssError::InternalError {
+			message: format!("Failed to decode JSON response: {}", e),
+			source: Some(Box::new(e)),
+			status: None,
+			request_id: None,
+		})
+	} else {
+		// Transcodes the `ErrorResponse` to protobuf, such that it's mapped like protobuf responses.
+		// Bodies without any known field are left as-is, to be included in the error message.
+		let payload = match serde_json::from_slice::<ErrorResponse>(&payload) {
+			Ok(error_response) if error_response != ErrorResponse::default() => {
+				error_response.encode_to_vec().into()
+			},
+			_ => payload,
+		};
+		Err(VssError::from_response(status, headers, payload))
+	}
+}
+
+// Encodes `int64` fields as strings, as per the canonical JSON mapping of protobuf, while also
+// accepting numbers.
+pub(crate) mod int64 {
+	use super::*;
+
+	pub(crate) fn serialize<S: Serializer>(value: &i64, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.collect_str(value)
+	}
+
+	pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+		Int64::deserialize(deserializer).map(|value| value.0)
+	}
+}
+
+// Like `int64`, for `optional int64` fields.
+pub(crate) mod optional_int64 {
+	use super::*;
+
+	pub(crate) fn serialize<S: Serializer>(
+		value: &Option<i64>, serializer: S,
+	) -> Result<S::Ok, S::Error> {
+		match value {
+			Some(value) => serializer.collect_str(value),
+			None => serializer.serialize_none(),
+		}
+	}
+
+	pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+		deserializer: D,
+	) -> Result<Option<i64>, D::Error> {
+		Option::<Int64>::deserialize(deserializer).map(|value| value.map(|value| value.0))
+	}
+}
+
+// Encodes `bytes` fields as base64 strings, as per the canonical JSON mapping of protobuf.
+pub(crate) mod bytes {
+	use super::*;
+
+	pub(crate) fn serialize<S: Serializer>(value: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_str(&STANDARD.encode(value))
+	}
+
+	pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+		deserializer: D,
+	) -> Result<Vec<u8>, D::Error> {
+		let value = String::deserialize(deserializer)?;
+		STANDARD.decode(value).map_err(de::Error::custom)
+	}
+}
+
+// Encodes `ErrorCode` fields by their name, as per the canonical JSON mapping of protobuf, while
+// also accepting their numeric value.
+pub(crate) mod error_code {
+	use super::*;
+
+	pub(crate) fn serialize<S: Serializer>(value: &i32, serializer: S) -> Result<S::Ok, S::Error> {
+		match ErrorCode::from_i32(*value) {
+			Some(error_code) => serializer.serialize_str(error_code.as_str_name()),
+			None => serializer.serialize_i32(*value),
+		}
+	}
+
+	pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i32, D::Error> {
+		struct ErrorCodeVisitor;
+
+		impl<'de> Visitor<'de> for ErrorCodeVisitor {
+			type Value = i32;
+
+			fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				f.write_str("an error code name or number")
+			}
+
+			fn visit_i64<E: de::Error>(self, value: i64) -> Result<i32, E> {
+				i32::try_from(value).map_err(E::custom)
+			}
+
+			fn visit_u64<E: de::Error>(self, value: u64) -> Result<i32, E> {
+				i32::try_from(value).map_err(E::custom)
+			}
+
+			fn visit_str<E: de::Error>(self, value: &str) -> Result<i32, E> {
+				match ErrorCode::from_str_name(value) {
+					Some(error_code) => Ok(error_code.into()),
+					None => Err(E::custom(format!("Unknown error code: {}", value))),
+				}
+			}
+		}
+
+		deserializer.deserialize_any(ErrorCodeVisitor)
+	}
+}
+
+struct Int64(i64);
+
+impl<'de> Deserialize<'de> for Int64 {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		struct Int64Visitor;
+
+		impl<'de> Visitor<'de> for Int64Visitor {
+			type Value = Int64;
+
+			fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				f.write_str("a 64-bit integer, or a string containing one")
+			}
+
+			fn visit_i64<E: de::Error>(self, value: i64) -> Result<Int64, E> {
+				Ok(Int64(value))
+			}
+
+			fn visit_u64<E: de::Error>(self, value: u64) -> Result<Int64, E> {
+				i64::try_from(value).map(Int64).map_err(E::custom)
+			}
+
+			fn visit_str<E: de::Error>(self, value: &str) -> Result<Int64, E> {
+				value.parse().map(Int64).map_err(E::custom)
+			}
+		}
+
+		deserializer.deserialize_any(Int64Visitor)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::{
+		GetObjectResponse, KeyValue, ListKeyVersionsRequest, ListKeyVersionsResponse,
+		PutObjectRequest,
+	};
+	use reqwest::header::HeaderValue;
+
+	fn headers(content_type: &'static str) -> HeaderMap {
+		let mut headers = HeaderMap::new();
+		headers.insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+		headers
+	}
+
+	#[test]
+	fn encodes_canonical_json() {
+		let request = PutObjectRequest {
+			store_id: "store".to_string(),
+			global_version: Some(3),
+			transaction_items: vec![KeyValue {
+				key: "k1".to_string(),
+				version: 2,
+				value: b"k1v3".to_vec(),
+			}],
+			delete_items: vec![],
+		};
+		let encoded = String::from_utf8(encode_request(&request).unwrap()).unwrap();
+		assert_eq!(
+			encoded,
+			r#"{"storeId":"store","globalVersion":"3","transactionItems":[{"key":"k1","version":"2","value":"azF2Mw=="}],"deleteItems":[]}"#
+		);
+		assert_eq!(serde_json::from_str::<PutObjectRequest>(&encoded).unwrap(), request);
+
+		// Unset optional fields are omitted.
+		let request = ListKeyVersionsRequest {
+			store_id: "store".to_string(),
+			key_prefix: None,
+			page_size: Some(10),
+			page_token: None,
+		};
+		let encoded = String::from_utf8(encode_request(&request).unwrap()).unwrap();
+		assert_eq!(encoded, r#"{"storeId":"store","pageSize":10}"#);
+	}
+
+	#[test]
+	fn decodes_lenient_json() {
+		// Numbers are accepted for `int64` fields, and missing fields take their default.
+		let payload = Bytes::from_static(
+			br#"{"keyVersions":[{"key":"k1","version":2}],"globalVersion":5,"unknownField":1}"#,
+		);
+		let response: ListKeyVersionsResponse =
+			decode_response(StatusCode::OK, &HeaderMap::new(), payload).unwrap();
+		assert_eq!(
+			response,
+			ListKeyVersionsResponse {
+				key_versions: vec![KeyValue { key: "k1".to_string(), version: 2, value: vec![] }],
+				next_page_token: None,
+				global_version: Some(5),
+			}
+		);
+
+		let payload = Bytes::from_static(b"not json");
+		let result =
+			decode_response::<GetObjectResponse>(StatusCode::OK, &HeaderMap::new(), payload);
+		assert!(matches!(result, Err(VssError::InternalError { .. })));
+	}
+
+	#[test]
+	fn decodes_json_error_responses() {
+		let payload =
+			Bytes::from_static(br#"{"errorCode":"NO_SUCH_KEY_EXCEPTION","message":"Not found"}"#);
+		let result =
+			decode_response::<GetObjectResponse>(StatusCode::NOT_FOUND, &HeaderMap::new(), payload);
+		assert!(matches!(result, Err(VssError::NoSuchKeyError { .. })));
+
+		let payload = Bytes::from_static(br#"{"errorCode":2,"message":"Invalid"}"#);
+		let result = decode_response::<GetObjectResponse>(
+			StatusCode::BAD_REQUEST,
+			&HeaderMap::new(),
+			payload,
+		);
+		assert!(matches!(result, Err(VssError::InvalidRequestError(..))));
+
+		// Bodies which aren't an `ErrorResponse` are mapped by the HTTP status.
+		let payload = Bytes::from_static(br#"{"error":"Bad gateway"}"#);
+		let result = decode_response::<GetObjectResponse>(
+			StatusCode::BAD_GATEWAY,
+			&HeaderMap::new(),
+			payload,
+		);
+		let error = result.unwrap_err();
+		assert!(matches!(error, VssError::InternalServerError { .. }));
+		assert!(error.to_string().contains("Bad gateway"));
+	}
+
+	#[test]
+	fn detects_json_responses() {
+		assert!(is_json_response(&headers("application/json")));
+		assert!(is_json_response(&headers("Application/JSON; charset=utf-8")));
+		assert!(!is_json_response(&headers("application/octet-stream")));
+		assert!(!is_json_response(&HeaderMap::new()));
+	}
+}
@@ -10,21 +10,94 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 #![deny(rustdoc::private_intra_doc_links)]
 #![deny(missing_docs)]
+#![allow(clippy::tabs_in_doc_comments)]
 
 /// Implements a thin-client ([`client::VssClient`]) to access a hosted instance of Versioned Storage Service (VSS).
 pub mod client;
 
+/// Implements a blocking thin-client ([`blocking::VssBlockingClient`]) to access a hosted instance of Versioned Storage Service (VSS),
+/// and a blocking wrapper ([`blocking::VssClientBlockingWrapper`]) around [`client::VssClient`].
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
+/// Contains the configuration of request compression ([`compression::CompressionMode`]).
+#[cfg(feature = "gzip")]
+pub mod compression;
+
+/// Contains the configuration of the JSON transport mode ([`json::TransportMode`]).
+#[cfg(feature = "json")]
+pub mod json;
+
+/// Contains the names of the metrics recorded for VSS operations via the [`metrics`](::metrics)
+/// facade.
+#[cfg(feature = "metrics")]
+pub mod metrics;
+
 /// Implements the error type ([`error::VssError`]) returned on interacting with [`client::VssClient`]
 pub mod error;
 
+/// Implements a builder for atomic transactions of writes and deletes ([`transaction::VssTransaction`]).
+pub mod transaction;
+
+/// Implements a client-side encrypting wrapper ([`encryption::EncryptedVssClient`]) around a
+/// [`client::VssApi`] implementation.
+pub mod encryption;
+
+/// Implements a wrapper ([`checksum::ChecksummedVssClient`]) around a [`client::VssApi`]
+/// implementation, verifying the integrity of values via checksums.
+pub mod checksum;
+
+/// Implements wrappers around [`client::VssApi`] implementations, failing fast while the VSS server
+/// is unavailable ([`resilience::CircuitBreakerVssClient`]) or failing over between its replicas
+/// ([`resilience::FailoverVssClient`]), and contains the configuration of client-side rate limiting
+/// ([`resilience::RateLimitBehavior`]).
+pub mod resilience;
+
+/// Implements a wrapper ([`queue::QueuedVssClient`]) around a [`client::VssApi`] implementation,
+/// durably queueing writes while the VSS server is unreachable, to be replayed later.
+pub mod queue;
+
+/// Implements a wrapper ([`cache::CachedVssClient`]) around a [`client::VssApi`] implementation,
+/// caching fetched values in memory.
+#[cfg(feature = "cache")]
+pub mod cache;
+
+/// Implements a wrapper ([`typed::TypedVssClient`]) around a [`client::VssApi`] implementation,
+/// providing access to serialized keys and values of given types.
+#[cfg(feature = "typed")]
+pub mod typed;
+
 /// Contains request/response types generated from the API definition of VSS.
+#[allow(clippy::doc_lazy_continuation)]
 pub mod types;
 
+/// Contains the client-side [`envelope::Storable`] and [`envelope::PlaintextBlob`] types used by
+/// [`util::storable_builder`], extending the ones of [`types`] while remaining wire-compatible with
+/// them.
+pub mod envelope;
+
 /// Contains utils for encryption, requests-retries etc.
 pub mod util;
 
 // Encryption-Decryption related crate-only helpers.
+#[allow(clippy::all)]
 pub(crate) mod crypto;
 
 /// A collection of header providers.
 pub mod headers;
+
+/// Contains the traits for authenticating requests via bearer tokens ([`auth::AuthProvider`]) or
+/// request signatures ([`auth::RequestSigner`]).
+pub mod auth;
+
+/// Contains the trait for observing the outcome of requests ([`observer::VssRequestObserver`]).
+pub mod observer;
+
+/// Contains the trait for intercepting requests and their responses
+/// ([`interceptor::Interceptor`]), as well as a logging implementation of it, if the `logging`
+/// feature is enabled.
+pub mod interceptor;
+
+/// Contains test utilities, such as an in-memory implementation of [`client::VssApi`].
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
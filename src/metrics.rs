@@ -0,0 +1,94 @@
+use std::future::Future;
+use std::time::Instant;
+
+use crate::error::VssError;
+
+/// A counter of the requests made, labelled by `operation` (e.g. `get_object`) and `status`.
+///
+/// The `status` is either `success`, or the kind of error the operation failed with, e.g.
+/// `conflict`. Retries of an operation are not counted as separate requests.
+pub const REQUESTS_TOTAL: &str = "vss.requests.total";
+
+/// A histogram of the duration of requests in milliseconds, including any retries, labelled by
+/// `operation`.
+pub const REQUEST_DURATION_MS: &str = "vss.request.duration_ms";
+
+/// A counter of the retries performed after failed attempts.
+pub const RETRIES_TOTAL: &str = "vss.retries.total";
+
+/// A gauge of the requests currently in flight.
+pub const IN_FLIGHT_REQUESTS: &str = "vss.in_flight_requests";
+
+/// A counter of the values served from the cache of a `CachedVssClient`, available with the
+/// `cache` feature.
+pub const CACHE_HITS_TOTAL: &str = "vss.cache.hits.total";
+
+/// A counter of the values looked up but not found in the cache of a `CachedVssClient`,
+/// including expired ones.
+pub const CACHE_MISSES_TOTAL: &str = "vss.cache.misses.total";
+
+// Decrements the in-flight gauge once dropped, i.e. also when the operation is cancelled.
+struct InFlightGuard;
+
+impl InFlightGuard {
+	fn new() -> Self {
+		::metrics::gauge!(IN_FLIGHT_REQUESTS).increment(1.0);
+		InFlightGuard
+	}
+}
+
+impl Drop for InFlightGuard {
+	fn drop(&mut self) {
+		::metrics::gauge!(IN_FLIGHT_REQUESTS).decrement(1.0);
+	}
+}
+
+// Awaits the given operation, recording its metrics.
+pub(crate) async fn measure<T, F: Future<Output = Result<T, VssError>>>(
+	operation: &'static str, future: F,
+) -> Result<T, VssError> {
+	let _in_flight = InFlightGuard::new();
+	let start = Instant::now();
+	let result = future.await;
+	let duration_ms = start.elapsed().as_secs_f64() * 1000.0;
+	::metrics::histogram!(REQUEST_DURATION_MS, "operation" => operation).record(duration_ms);
+	let status = match &result {
+		Ok(_) => "success",
+		Err(e) => error_kind(e),
+	};
+	::metrics::counter!(REQUESTS_TOTAL, "operation" => operation, "status" => status).increment(1);
+	result
+}
+
+pub(crate) fn record_retry() {
+	::metrics::counter!(RETRIES_TOTAL).increment(1);
+}
+
+#[cfg(feature = "cache")]
+pub(crate) fn record_cache_lookup(hit: bool) {
+	let name = if hit { CACHE_HITS_TOTAL } else { CACHE_MISSES_TOTAL };
+	::metrics::counter!(name).increment(1);
+}
+
+fn error_kind(error: &VssError) -> &'static str {
+	match error {
+		VssError::NoSuchKeyError { .. } => "no_such_key",
+		VssError::InvalidRequestError(..) => "invalid_request",
+		VssError::ConflictError { .. } => "conflict",
+		VssError::AlreadyExistsError(..) => "already_exists",
+		VssError::CasConflictError(..) => "cas_conflict",
+		VssError::GlobalVersionConflictError { .. } => "global_version_conflict",
+		VssError::AuthError(..) => "auth",
+		VssError::InternalServerError { .. } => "internal_server",
+		VssError::InternalError { .. } => "internal",
+		VssError::RateLimitedError { .. } => "rate_limited",
+		VssError::TimeoutError { .. } => "timeout",
+		VssError::InvalidArgumentError(..) => "invalid_argument",
+		VssError::CircuitOpenError(..) => "circuit_open",
+		VssError::TlsConfigurationError(..) => "tls_configuration",
+		VssError::SerializationError(..) => "serialization",
+		VssError::ChecksumMismatchError(..) => "checksum_mismatch",
+		VssError::ChunkedValueError(..) => "chunked_value",
+		VssError::DecryptionError(..) => "decryption",
+	}
+}
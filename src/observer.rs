@@ -0,0 +1,50 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::error::VssError;
+
+/// The operations of the VSS server API, as reported to a [`VssRequestObserver`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VssOperation {
+	/// A call to the `GetObject` endpoint.
+	GetObject,
+	/// A call to the `PutObjects` endpoint.
+	PutObject,
+	/// A call to the `DeleteObject` endpoint.
+	DeleteObject,
+	/// A call to the `ListKeyVersions` endpoint.
+	ListKeyVersions,
+}
+
+impl fmt::Display for VssOperation {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			VssOperation::GetObject => write!(f, "GetObject"),
+			VssOperation::PutObject => write!(f, "PutObject"),
+			VssOperation::DeleteObject => write!(f, "DeleteObject"),
+			VssOperation::ListKeyVersions => write!(f, "ListKeyVersions"),
+		}
+	}
+}
+
+/// Observes the outcome of requests made to the VSS server, e.g. to feed them into monitoring.
+///
+/// Please refer to [`VssClientBuilder::observer`] for configuring a [`VssClient`] to use it.
+///
+/// [`VssClient`]: crate::client::VssClient
+/// [`VssClientBuilder::observer`]: crate::client::VssClientBuilder::observer
+pub trait VssRequestObserver: Send + Sync {
+	/// Called once an attempt of the given `operation` completed, i.e. for every attempt including
+	/// retried ones, after it took `duration`.
+	///
+	/// The `attempt` counts the attempts of the operation, starting at `1`. The `result` is either
+	/// `Ok` or the error the attempt failed with, which may be classified via
+	/// [`VssError::status_code`] or [`VssError::is_retryable`].
+	///
+	/// This is called inline on the request path, hence should return quickly, and is not able to
+	/// affect the request. Panics are caught and ignored, unless built with `panic = "abort"`.
+	fn on_request_completed(
+		&self, operation: VssOperation, duration: Duration, attempt: u32,
+		result: Result<(), &VssError>,
+	);
+}
@@ -0,0 +1,524 @@
+use async_trait::async_trait;
+use prost::Message;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tokio::sync::Mutex as AsyncMutex;
+
+use crate::client::VssApi;
+use crate::error::VssError;
+use crate::types::{
+	DeleteObjectRequest, DeleteObjectResponse, GetObjectRequest, GetObjectResponse,
+	ListKeyVersionsRequest, ListKeyVersionsResponse, PutObjectRequest, PutObjectResponse,
+};
+
+/// A durable FIFO queue of writes, pending to be replayed by a [`QueuedVssClient`].
+///
+/// Entries are opaque, encoded [`PutObjectRequest`]s. An entry must be persisted durably before
+/// [`WriteQueue::push`] returns, as the write is considered accepted once queued.
+#[async_trait]
+pub trait WriteQueue: Send + Sync {
+	/// Appends `entry` to the end of the queue.
+	async fn push(&self, entry: &[u8]) -> Result<(), VssError>;
+
+	/// Returns the entry at the head of the queue without removing it, if any.
+	async fn peek(&self) -> Result<Option<Vec<u8>>, VssError>;
+
+	/// Removes and returns the entry at the head of the queue, if any.
+	async fn pop(&self) -> Result<Option<Vec<u8>>, VssError>;
+}
+
+/// A [`WriteQueue`] persisted in a single file.
+///
+/// The file is rewritten on each change, by writing to a temporary file next to it which then
+/// replaces it, such that it's never left partially written. Hence, it's meant for the small number
+/// of writes accumulating while offline, rather than as a general-purpose queue.
+///
+/// **Note**: The file is written and synced to disk synchronously, i.e. [`WriteQueue::push`] and
+/// [`WriteQueue::pop`] block the thread of the calling task until done, which is negligible for
+/// such a small file on a local disk. If this isn't acceptable, e.g. for a slow or network-backed
+/// file system, implement [`WriteQueue`] on top of asynchronous I/O instead.
+pub struct FileWriteQueue {
+	path: PathBuf,
+	entries: Mutex<VecDeque<Vec<u8>>>,
+}
+
+impl FileWriteQueue {
+	/// Opens the queue persisted at `path`, or an empty one if the file doesn't exist yet.
+	///
+	/// Fails with a [`VssError::InternalError`] if the file can't be read or is malformed.
+	pub fn open(path: PathBuf) -> Result<Self, VssError> {
+		let entries = match fs::read(&path) {
+			Ok(contents) => decode_entries(&contents).ok_or_else(|| VssError::InternalError {
+				message: format!("Malformed write queue at {}", path.display()),
+				source: None,
+				status: None,
+				request_id: None,
+			})?,
+			Err(e) if e.kind() == io::ErrorKind::NotFound => VecDeque::new(),
+			Err(e) => return Err(io_error(&path, e)),
+		};
+		Ok(Self { path, entries: Mutex::new(entries) })
+	}
+
+	/// Returns the path of the file the queue is persisted in.
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+
+	fn persist(&self, entries: &VecDeque<Vec<u8>>) -> Result<(), VssError> {
+		let mut tmp_path = self.path.clone().into_os_string();
+		tmp_path.push(".tmp");
+		let tmp_path = PathBuf::from(tmp_path);
+		let write = || -> io::Result<()> {
+			let mut file = fs::File::create(&tmp_path)?;
+			file.write_all(&encode_entries(entries))?;
+			file.sync_all()?;
+			fs::rename(&tmp_path, &self.path)
+		};
+		write().map_err(|e| io_error(&self.path, e))
+	}
+}
+
+#[async_trait]
+impl WriteQueue for FileWriteQueue {
+	async fn push(&self, entry: &[u8]) -> Result<(), VssError> {
+		let mut entries = self.entries.lock().unwrap();
+		entries.push_back(entry.to_vec());
+		if let Err(e) = self.persist(&entries) {
+			entries.pop_back();
+			return Err(e);
+		}
+		Ok(())
+	}
+
+	async fn peek(&self) -> Result<Option<Vec<u8>>, VssError> {
+		Ok(self.entries.lock().unwrap().front().cloned())
+	}
+
+	async fn pop(&self) -> Result<Option<Vec<u8>>, VssError> {
+		let mut entries = self.entries.lock().unwrap();
+		let entry = match entries.pop_front() {
+			Some(entry) => entry,
+			None => return Ok(None),
+		};
+		if let Err(e) = self.persist(&entries) {
+			entries.push_front(entry);
+			return Err(e);
+		}
+		Ok(Some(entry))
+	}
+}
+
+// Encodes each entry prefixed by its big-endian `u32` length.
+fn encode_entries(entries: &VecDeque<Vec<u8>>) -> Vec<u8> {
+	let mut contents = Vec::with_capacity(entries.iter().map(|entry| 4 + entry.len()).sum());
+	for entry in entries {
+		contents.extend_from_slice(&(entry.len() as u32).to_be_bytes());
+		contents.extend_from_slice(entry);
+	}
+	contents
+}
+
+fn decode_entries(mut contents: &[u8]) -> Option<VecDeque<Vec<u8>>> {
+	let mut entries = VecDeque::new();
+	while !contents.is_empty() {
+		if contents.len() < 4 {
+			return None;
+		}
+		let (length, rest) = contents.split_at(4);
+		let mut length_bytes = [0u8; 4];
+		length_bytes.copy_from_slice(length);
+		let length = u32::from_be_bytes(length_bytes) as usize;
+		if rest.len() < length {
+			return None;
+		}
+		let (entry, rest) = rest.split_at(length);
+		entries.push_back(entry.to_vec());
+		contents = rest;
+	}
+	Some(entries)
+}
+
+fn io_error(path: &Path, e: io::Error) -> VssError {
+	VssError::InternalError {
+		message: format!("Failed to access write queue at {}: {}", path.display(), e),
+		source: Some(Box::new(e)),
+		status: None,
+		request_id: None,
+	}
+}
+
+/// The failure of replaying a queued write via [`QueuedVssClient::flush`].
+#[derive(Debug)]
+pub struct FlushError {
+	/// The queued write which failed to be replayed, or `None` if it couldn't be read from the
+	/// [`WriteQueue`] or decoded.
+	pub request: Option<PutObjectRequest>,
+	/// The error replaying `request` failed with.
+	pub error: VssError,
+	/// Whether `request` remains at the head of the queue, as it failed due to a transport failure
+	/// and will be replayed by the next flush. Otherwise, e.g. if it conflicted, it was removed
+	/// from the queue, to be reconciled by the application.
+	pub remains_queued: bool,
+	/// The number of queued writes replayed successfully before `request`.
+	pub flushed_count: usize,
+}
+
+impl Display for FlushError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match &self.request {
+			Some(request) => {
+				write!(
+					f,
+					"Failed to replay queued write to store: {}: {}",
+					request.store_id, self.error
+				)
+			},
+			None => write!(f, "Failed to read queued write: {}", self.error),
+		}
+	}
+}
+
+impl Error for FlushError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		Some(&self.error)
+	}
+}
+
+/// A wrapper around a [`VssApi`] implementation, queueing writes which failed due to a transport
+/// failure in a durable [`WriteQueue`], to be replayed via [`QueuedVssClient::flush`] once
+/// connectivity returns.
+///
+/// A [`VssApi::put_object`] failing with a [`VssError::TimeoutError`], or with a
+/// [`VssError::InternalError`] as a connection to the server couldn't be established or the request
+/// couldn't be sent, is queued, after any retries of the wrapped implementation were exhausted.
+/// Once queued, the write succeeds with an empty [`PutObjectResponse`]. As long as writes are
+/// queued, and while they're being flushed, further writes are queued behind them rather than
+/// being sent, preserving their order. Any other error is returned as-is, without queueing.
+///
+/// Note that a write which timed out may have been applied nonetheless, in which case replaying it
+/// conflicts if it's conditioned on a version. Likewise, writes are replayed at least once, e.g. if
+/// the process is terminated after a write was replayed but before it was removed from the queue.
+///
+/// Reads, i.e. [`VssApi::get_object`] and [`VssApi::list_key_versions`], bypass the queue, hence
+/// don't observe queued writes. A [`VssApi::delete_object`] fails with a [`VssError::InternalError`]
+/// while writes are queued or being flushed, as it would otherwise be applied before them, e.g.
+/// resurrecting a deleted key once an earlier write to it is replayed. Deletes may be retried once
+/// the queue was flushed.
+pub struct QueuedVssClient<C: VssApi, Q: WriteQueue> {
+	inner: C,
+	queue: Q,
+	flush_lock: AsyncMutex<()>,
+	flushing: AtomicBool,
+}
+
+impl<C: VssApi, Q: WriteQueue> QueuedVssClient<C, Q> {
+	/// Constructs a new instance wrapping `inner`, queueing writes in `queue`.
+	///
+	/// Writes remaining in `queue`, e.g. from a previous run, are replayed by the next flush.
+	pub fn new(inner: C, queue: Q) -> Self {
+		Self { inner, queue, flush_lock: AsyncMutex::new(()), flushing: AtomicBool::new(false) }
+	}
+
+	/// Returns the wrapped [`VssApi`] implementation.
+	pub fn inner(&self) -> &C {
+		&self.inner
+	}
+
+	/// Returns the [`WriteQueue`] writes are queued in.
+	pub fn queue(&self) -> &Q {
+		&self.queue
+	}
+
+	/// Replays the queued writes in order, removing each from the queue once it succeeded.
+	///
+	/// Returns the number of replayed writes once the queue is empty. The flush stops at the first
+	/// write failing to be replayed, which is returned as a [`FlushError`] along with its error,
+	/// leaving the writes behind it queued. A write failing due to a transport failure remains
+	/// queued, while one failing otherwise, e.g. with a [`VssError::ConflictError`], is removed from
+	/// the queue, to be reconciled by the application, e.g. by fetching the current value and
+	/// writing it anew.
+	///
+	/// Errors of the [`WriteQueue`] itself are returned as a [`FlushError`] with the write being
+	/// replayed, if any, remaining queued. A queued write which can't be decoded is removed from the
+	/// queue, and returned as a [`FlushError`] without a request.
+	pub async fn flush(&self) -> Result<usize, FlushError> {
+		let _flush_guard = self.flush_lock.lock().await;
+		self.flushing.store(true, Ordering::SeqCst);
+		let _flushing_guard = FlushingGuard(&self.flushing);
+		let mut flushed_count = 0;
+		let queue_error = |error, request, flushed_count| FlushError {
+			request,
+			error,
+			remains_queued: true,
+			flushed_count,
+		};
+		loop {
+			let entry = match self.queue.peek().await {
+				Ok(Some(entry)) => entry,
+				Ok(None) => return Ok(flushed_count),
+				Err(error) => return Err(queue_error(error, None, flushed_count)),
+			};
+			let request = match decode_request(&entry) {
+				Ok(request) => request,
+				Err(error) => {
+					// A malformed entry would fail to be decoded on every flush, hence it's removed
+					// rather than blocking the writes behind it.
+					if let Err(error) = self.queue.pop().await {
+						return Err(queue_error(error, None, flushed_count));
+					}
+					let remains_queued = false;
+					return Err(FlushError { request: None, error, remains_queued, flushed_count });
+				},
+			};
+			let error = self.inner.put_object(&request).await.err();
+			let remains_queued = error.as_ref().map_or(false, VssError::is_transport_failure);
+			if !remains_queued {
+				if let Err(error) = self.queue.pop().await {
+					return Err(queue_error(error, Some(request), flushed_count));
+				}
+			}
+			match error {
+				None => flushed_count += 1,
+				Some(error) => {
+					let request = Some(request);
+					return Err(FlushError { request, error, remains_queued, flushed_count });
+				},
+			}
+		}
+	}
+
+	async fn enqueue(&self, request: &PutObjectRequest) -> Result<PutObjectResponse, VssError> {
+		self.queue.push(&request.encode_to_vec()).await?;
+		Ok(PutObjectResponse {})
+	}
+}
+
+// Resets the flag marking a flush in progress on drop, including if the flush is cancelled.
+struct FlushingGuard<'a>(&'a AtomicBool);
+
+impl Drop for FlushingGuard<'_> {
+	fn drop(&mut self) {
+		self.0.store(false, Ordering::SeqCst);
+	}
+}
+
+#[async_trait]
+impl<C: VssApi, Q: WriteQueue> VssApi for QueuedVssClient<C, Q> {
+	async fn get_object(&self, request: &GetObjectRequest) -> Result<GetObjectResponse, VssError> {
+		self.inner.get_object(request).await
+	}
+
+	async fn put_object(&self, request: &PutObjectRequest) -> Result<PutObjectResponse, VssError> {
+		// Writes are queued behind pending ones, including those being flushed.
+		if self.flushing.load(Ordering::SeqCst) || self.queue.peek().await?.is_some() {
+			return self.enqueue(request).await;
+		}
+		match self.inner.put_object(request).await {
+			Err(error) if error.is_transport_failure() => self.enqueue(request).await,
+			result => result,
+		}
+	}
+
+	async fn delete_object(
+		&self, request: &DeleteObjectRequest,
+	) -> Result<DeleteObjectResponse, VssError> {
+		if self.flushing.load(Ordering::SeqCst) || self.queue.peek().await?.is_some() {
+			return Err(VssError::InternalError {
+				message: "Failed to delete while writes are queued, they need to be flushed first"
+					.to_string(),
+				source: None,
+				status: None,
+				request_id: None,
+			});
+		}
+		self.inner.delete_object(request).await
+	}
+
+	async fn list_key_versions(
+		&self, request: &ListKeyVersionsRequest,
+	) -> Result<ListKeyVersionsResponse, VssError> {
+		self.inner.list_key_versions(request).await
+	}
+}
+
+fn decode_request(entry: &[u8]) -> Result<PutObjectRequest, VssError> {
+	PutObjectRequest::decode(entry).map_err(|e| VssError::InternalError {
+		message: format!("Failed to decode queued write: {}", e),
+		source: Some(Box::new(e)),
+		status: None,
+		request_id: None,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_utils::{get_request, put_request, Fault, FlakyVssClient};
+	use crate::types::KeyValue;
+	use rand::RngCore;
+	use std::net::TcpListener;
+
+	fn queue_path() -> PathBuf {
+		let mut suffix = [0u8; 8];
+		rand::thread_rng().fill_bytes(&mut suffix);
+		let name = format!("vss-write-queue-{}", u64::from_be_bytes(suffix));
+		std::env::temp_dir().join(name)
+	}
+
+	fn queued_client() -> QueuedVssClient<FlakyVssClient, FileWriteQueue> {
+		let queue = FileWriteQueue::open(queue_path()).unwrap();
+		let inner = FlakyVssClient::new();
+		inner.fail_with(Some(Fault::Timeout));
+		QueuedVssClient::new(inner, queue)
+	}
+
+	#[tokio::test]
+	async fn queues_writes_on_transport_failure() {
+		let client = queued_client();
+		client.put_object(&put_request("k1", 0, b"k1v1")).await.unwrap();
+		let entry = client.queue().peek().await.unwrap().unwrap();
+		assert_eq!(decode_request(&entry).unwrap(), put_request("k1", 0, b"k1v1"));
+
+		// Once online, further writes are queued behind the pending one, and reads bypass the queue.
+		client.inner().fail_with(None);
+		client.put_object(&put_request("k2", 0, b"k2v1")).await.unwrap();
+		assert!(client.get_object(&get_request("k1")).await.unwrap_err().is_not_found());
+		assert!(client.get_object(&get_request("k2")).await.unwrap_err().is_not_found());
+
+		// Other errors aren't queued.
+		assert_eq!(client.flush().await.unwrap(), 2);
+		let result = client.put_object(&put_request("k1", 0, b"k1v1")).await;
+		assert!(matches!(result, Err(VssError::ConflictError { .. })));
+		assert_eq!(client.queue().peek().await.unwrap(), None);
+		fs::remove_file(client.queue().path()).unwrap();
+	}
+
+	#[tokio::test]
+	async fn replays_queued_writes_in_order() {
+		let client = queued_client();
+		client.put_object(&put_request("k1", 0, b"k1v1")).await.unwrap();
+		client.put_object(&put_request("k1", 1, b"k1v2")).await.unwrap();
+		client.put_object(&put_request("k2", 0, b"k2v1")).await.unwrap();
+
+		// Flushing while offline leaves the writes queued.
+		let error = client.flush().await.unwrap_err();
+		assert!(error.remains_queued);
+		assert_eq!(error.flushed_count, 0);
+		assert_eq!(error.request, Some(put_request("k1", 0, b"k1v1")));
+
+		// The queue survives a restart, and its writes are replayed in order.
+		let path = client.queue().path().to_path_buf();
+		let client =
+			QueuedVssClient::new(FlakyVssClient::new(), FileWriteQueue::open(path).unwrap());
+		assert_eq!(client.flush().await.unwrap(), 3);
+		let value = client.get_object(&get_request("k1")).await.unwrap().value.unwrap();
+		assert_eq!((value.version, value.value), (2, b"k1v2".to_vec()));
+		let value = client.get_object(&get_request("k2")).await.unwrap().value.unwrap();
+		assert_eq!(value.value, b"k2v1".to_vec());
+		assert_eq!(client.flush().await.unwrap(), 0);
+		fs::remove_file(client.queue().path()).unwrap();
+	}
+
+	#[tokio::test]
+	async fn conflicts_abort_flush() {
+		let client = queued_client();
+		client.put_object(&put_request("k1", 0, b"k1v1")).await.unwrap();
+		client.put_object(&put_request("k2", 0, b"k2v1")).await.unwrap();
+		client.put_object(&put_request("k3", 0, b"k3v1")).await.unwrap();
+
+		// `k2` was written concurrently, e.g. by another device.
+		client.inner().inner.put_object(&put_request("k2", 0, b"other")).await.unwrap();
+		client.inner().fail_with(None);
+		let error = client.flush().await.unwrap_err();
+		assert!(matches!(error.error, VssError::ConflictError { .. }));
+		assert_eq!(error.request, Some(put_request("k2", 0, b"k2v1")));
+		assert!(!error.remains_queued);
+		assert_eq!(error.flushed_count, 1);
+
+		// The conflicting write was removed, while the one behind it remains queued.
+		assert!(client.get_object(&get_request("k3")).await.unwrap_err().is_not_found());
+		let entry = client.queue().peek().await.unwrap().unwrap();
+		assert_eq!(decode_request(&entry).unwrap(), put_request("k3", 0, b"k3v1"));
+		assert_eq!(client.flush().await.unwrap(), 1);
+		fs::remove_file(client.queue().path()).unwrap();
+	}
+
+	#[tokio::test]
+	async fn rejects_deletes_while_writes_are_queued() {
+		let client = queued_client();
+		client.put_object(&put_request("k1", 0, b"k1v1")).await.unwrap();
+		client.inner().fail_with(None);
+
+		// Deleting ahead of the queued write would have it resurrect the key once flushed.
+		let delete_request = DeleteObjectRequest {
+			store_id: "store".to_string(),
+			key_value: Some(KeyValue { key: "k1".to_string(), version: -1, value: vec![] }),
+		};
+		let result = client.delete_object(&delete_request).await;
+		assert!(matches!(result, Err(VssError::InternalError { .. })));
+
+		assert_eq!(client.flush().await.unwrap(), 1);
+		client.delete_object(&delete_request).await.unwrap();
+		assert!(client.get_object(&get_request("k1")).await.unwrap_err().is_not_found());
+		fs::remove_file(client.queue().path()).unwrap();
+	}
+
+	#[tokio::test]
+	async fn removes_undecodable_writes() {
+		let client = queued_client();
+		client.queue().push(b"malformed").await.unwrap();
+		client.put_object(&put_request("k1", 0, b"k1v1")).await.unwrap();
+		client.inner().fail_with(None);
+
+		// The malformed write is removed, rather than blocking the one behind it.
+		let error = client.flush().await.unwrap_err();
+		assert!(matches!(error.error, VssError::InternalError { .. }));
+		assert_eq!(error.request, None);
+		assert!(!error.remains_queued);
+		assert_eq!(error.flushed_count, 0);
+		assert_eq!(client.flush().await.unwrap(), 1);
+		assert!(client.get_object(&get_request("k1")).await.is_ok());
+		fs::remove_file(client.queue().path()).unwrap();
+	}
+
+	#[tokio::test]
+	async fn classifies_transport_failures() {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let url = format!("http://{}", listener.local_addr().unwrap());
+		drop(listener);
+		let error: VssError = reqwest::Client::new().post(url).send().await.unwrap_err().into();
+		assert!(error.is_transport_failure());
+
+		let error = VssError::InternalServerError {
+			message: "message".to_string(),
+			status: None,
+			request_id: None,
+		};
+		assert!(!error.is_transport_failure());
+		let error = VssError::InternalError {
+			message: "Failed to decode response".to_string(),
+			source: None,
+			status: None,
+			request_id: None,
+		};
+		assert!(!error.is_transport_failure());
+	}
+
+	#[test]
+	fn rejects_malformed_queue_file() {
+		let path = queue_path();
+		let mut entries = VecDeque::new();
+		entries.push_back(b"entry".to_vec());
+		let contents = encode_entries(&entries);
+		fs::write(&path, &contents[..contents.len() - 1]).unwrap();
+		assert!(matches!(FileWriteQueue::open(path.clone()), Err(VssError::InternalError { .. })));
+		fs::remove_file(path).unwrap();
+	}
+}
@@ -0,0 +1,489 @@
+use async_trait::async_trait;
+use std::future::Future;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::client::VssApi;
+use crate::error::VssError;
+use crate::types::{
+	DeleteObjectRequest, DeleteObjectResponse, GetObjectRequest, GetObjectResponse,
+	ListKeyVersionsRequest, ListKeyVersionsResponse, PutObjectRequest, PutObjectResponse,
+};
+
+/// Defines how requests exceeding the rate limit of a [`VssClient`] are handled.
+///
+/// Please refer to [`VssClientBuilder::rate_limit`].
+///
+/// [`VssClient`]: crate::client::VssClient
+/// [`VssClientBuilder::rate_limit`]: crate::client::VssClientBuilder::rate_limit
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RateLimitBehavior {
+	/// Requests are delayed until they may be sent.
+	#[default]
+	Wait,
+	/// Requests fail right away with a [`VssError::RateLimitedError`], carrying the delay until
+	/// they may be sent as its `retry_after`.
+	///
+	/// Note that, like the same error returned by the server, it is retryable, such that the retry
+	/// policy may retry the request, e.g. after the given delay if configured via
+	/// [`RetryPolicy::with_retry_after`]. To fail the operation right away instead, retries can be
+	/// skipped via [`RetryPolicy::skip_retry_on_error`].
+	///
+	/// [`RetryPolicy::with_retry_after`]: crate::util::retry::RetryPolicy::with_retry_after
+	/// [`RetryPolicy::skip_retry_on_error`]: crate::util::retry::RetryPolicy::skip_retry_on_error
+	Fail,
+}
+
+/// The state of the circuit of a [`CircuitBreakerVssClient`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CircuitState {
+	/// Calls are passed through to the wrapped implementation.
+	Closed,
+	/// Calls fail immediately with [`VssError::CircuitOpenError`].
+	Open,
+	/// Calls are passed through to probe whether the server recovered.
+	HalfOpen,
+}
+
+/// A wrapper around a [`VssApi`] implementation, failing fast while the VSS server is deemed to be
+/// unavailable, rather than having every call wait for its requests to time out.
+///
+/// After `failure_threshold` consecutive failed calls, i.e. ones failing with
+/// [`VssError::InternalServerError`], [`VssError::InternalError`] or [`VssError::TimeoutError`],
+/// the circuit opens and all calls fail immediately with [`VssError::CircuitOpenError`] for the
+/// `open_duration`. Afterwards, the circuit is half-open and calls are passed through again: once
+/// `success_threshold` of them succeeded in a row the circuit closes, while any failure re-opens it
+/// for another `open_duration`. Any other error is a response from a healthy server and counts as
+/// a success.
+///
+/// The circuit is shared by all operations, such that e.g. failing `put_object` calls also cause
+/// `get_object` calls to fail fast.
+///
+/// This operates on whole calls, including any retries performed by the wrapped implementation.
+/// To fail fast on individual attempts of a [`VssClient`] instead, refer to
+/// [`RetryPolicy::with_circuit_breaker`].
+///
+/// [`VssClient`]: crate::client::VssClient
+/// [`RetryPolicy::with_circuit_breaker`]: crate::util::retry::RetryPolicy::with_circuit_breaker
+pub struct CircuitBreakerVssClient<C: VssApi> {
+	inner: C,
+	failure_threshold: u32,
+	success_threshold: u32,
+	open_duration: Duration,
+	state: Mutex<CircuitStatus>,
+}
+
+#[derive(Clone, Copy)]
+enum CircuitStatus {
+	Closed { consecutive_failures: u32 },
+	Open { since: Instant, consecutive_failures: u32 },
+	HalfOpen { consecutive_successes: u32 },
+}
+
+impl<C: VssApi> CircuitBreakerVssClient<C> {
+	/// Constructs a new instance wrapping `inner`, with a closed circuit.
+	///
+	/// Both `failure_threshold` and `success_threshold` are at least `1`.
+	pub fn new(
+		inner: C, failure_threshold: u32, success_threshold: u32, open_duration: Duration,
+	) -> Self {
+		Self {
+			inner,
+			failure_threshold: failure_threshold.max(1),
+			success_threshold: success_threshold.max(1),
+			open_duration,
+			state: Mutex::new(CircuitStatus::Closed { consecutive_failures: 0 }),
+		}
+	}
+
+	/// Returns the wrapped [`VssApi`] implementation.
+	pub fn inner(&self) -> &C {
+		&self.inner
+	}
+
+	/// Returns the current state of the circuit.
+	pub fn state(&self) -> CircuitState {
+		match *self.state.lock().unwrap() {
+			CircuitStatus::Closed { .. } => CircuitState::Closed,
+			CircuitStatus::Open { since, .. } if since.elapsed() < self.open_duration => {
+				CircuitState::Open
+			},
+			CircuitStatus::Open { .. } | CircuitStatus::HalfOpen { .. } => CircuitState::HalfOpen,
+		}
+	}
+
+	// Awaits the given call, unless the circuit is open, updating the circuit with its outcome.
+	async fn call<T, F: Future<Output = Result<T, VssError>>>(
+		&self, call: F,
+	) -> Result<T, VssError> {
+		self.before_call()?;
+		let result = call.await;
+		match &result {
+			Err(e) if e.is_server_failure() => self.on_failure(),
+			// The wrapped implementation failed fast itself, which tells nothing about the server.
+			Err(VssError::CircuitOpenError(..)) => {},
+			_ => self.on_success(),
+		}
+		result
+	}
+
+	fn before_call(&self) -> Result<(), VssError> {
+		let mut state = self.state.lock().unwrap();
+		if let CircuitStatus::Open { since, consecutive_failures } = *state {
+			if since.elapsed() < self.open_duration {
+				return Err(VssError::CircuitOpenError(format!(
+					"Circuit opened after {} consecutive failures",
+					consecutive_failures
+				)));
+			}
+			*state = CircuitStatus::HalfOpen { consecutive_successes: 0 };
+		}
+		Ok(())
+	}
+
+	fn on_success(&self) {
+		let mut state = self.state.lock().unwrap();
+		*state = match *state {
+			CircuitStatus::HalfOpen { consecutive_successes }
+				if consecutive_successes + 1 < self.success_threshold =>
+			{
+				CircuitStatus::HalfOpen { consecutive_successes: consecutive_successes + 1 }
+			},
+			// A call started before the circuit opened doesn't close it again.
+			CircuitStatus::Open { since, consecutive_failures } => {
+				CircuitStatus::Open { since, consecutive_failures }
+			},
+			_ => CircuitStatus::Closed { consecutive_failures: 0 },
+		};
+	}
+
+	fn on_failure(&self) {
+		let mut state = self.state.lock().unwrap();
+		*state = match *state {
+			CircuitStatus::Closed { consecutive_failures }
+				if consecutive_failures + 1 < self.failure_threshold =>
+			{
+				CircuitStatus::Closed { consecutive_failures: consecutive_failures + 1 }
+			},
+			CircuitStatus::Closed { consecutive_failures } => CircuitStatus::Open {
+				since: Instant::now(),
+				consecutive_failures: consecutive_failures + 1,
+			},
+			CircuitStatus::HalfOpen { .. } => {
+				CircuitStatus::Open { since: Instant::now(), consecutive_failures: 1 }
+			},
+			CircuitStatus::Open { since, consecutive_failures } => CircuitStatus::Open {
+				since,
+				consecutive_failures: consecutive_failures.saturating_add(1),
+			},
+		};
+	}
+}
+
+#[async_trait]
+impl<C: VssApi> VssApi for CircuitBreakerVssClient<C> {
+	async fn get_object(&self, request: &GetObjectRequest) -> Result<GetObjectResponse, VssError> {
+		self.call(self.inner.get_object(request)).await
+	}
+
+	async fn put_object(&self, request: &PutObjectRequest) -> Result<PutObjectResponse, VssError> {
+		self.call(self.inner.put_object(request)).await
+	}
+
+	async fn delete_object(
+		&self, request: &DeleteObjectRequest,
+	) -> Result<DeleteObjectResponse, VssError> {
+		self.call(self.inner.delete_object(request)).await
+	}
+
+	async fn list_key_versions(
+		&self, request: &ListKeyVersionsRequest,
+	) -> Result<ListKeyVersionsResponse, VssError> {
+		self.call(self.inner.list_key_versions(request)).await
+	}
+}
+
+/// Defines when a [`FailoverVssClient`] tries a failed base URL again, before the base URLs
+/// following it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecoveryPolicy {
+	/// Failed base URLs are only tried again once all other base URLs failed as well.
+	Never,
+	/// Failed base URLs are probed by the first call made after the given interval passed since
+	/// they last failed, being re-promoted if it succeeds.
+	ProbeAfter(Duration),
+}
+
+/// A wrapper around [`VssApi`] implementations for multiple replicas of the VSS server, falling
+/// back to the next base URL while the preceding ones are failing.
+///
+/// Calls are made to the first healthy base URL, in the given order. A call failing with a
+/// server-side or network error, i.e. an error which [`VssError::is_transient`] other than a
+/// [`VssError::RateLimitedError`], marks its base URL as failed, and is retried against the next
+/// one, until all base URLs were tried, in which case the last error is returned. Any other error
+/// is a response from a healthy server and is returned right away. Failed base URLs are tried
+/// again as per the [`RecoveryPolicy`].
+///
+/// Which base URLs are healthy is shared by all operations and clones of this client.
+///
+/// This operates on whole calls, including any retries performed by the wrapped implementations,
+/// such that these should retry only briefly, if at all, to fail over quickly.
+pub struct FailoverVssClient<C: VssApi> {
+	base_urls: Arc<Vec<String>>,
+	clients: Arc<Vec<C>>,
+	recovery_policy: RecoveryPolicy,
+	state: Arc<RwLock<FailoverState>>,
+}
+
+// The time each base URL last failed at, if it's currently deemed to be failed.
+struct FailoverState {
+	failed_since: Vec<Option<Instant>>,
+}
+
+impl<C: VssApi> FailoverVssClient<C> {
+	/// Constructs a new instance for the given `base_urls`, in order of preference, building the
+	/// client for each via `build_client`, e.g.
+	/// `|base_url| VssClientBuilder::new(base_url, NoRetryPolicy::new()).build()`.
+	///
+	/// Fails with [`VssError::InvalidArgumentError`] if `base_urls` is empty, or with the error of
+	/// `build_client`.
+	pub fn new<F: FnMut(String) -> Result<C, VssError>>(
+		base_urls: Vec<String>, recovery_policy: RecoveryPolicy, mut build_client: F,
+	) -> Result<Self, VssError> {
+		if base_urls.is_empty() {
+			return Err(VssError::InvalidArgumentError(
+				"At least one base URL is required".to_string(),
+			));
+		}
+		let clients = base_urls
+			.iter()
+			.map(|base_url| build_client(base_url.clone()))
+			.collect::<Result<Vec<_>, VssError>>()?;
+		let state = FailoverState { failed_since: vec![None; clients.len()] };
+		Ok(Self {
+			base_urls: Arc::new(base_urls),
+			clients: Arc::new(clients),
+			recovery_policy,
+			state: Arc::new(RwLock::new(state)),
+		})
+	}
+
+	/// Returns the wrapped [`VssApi`] implementations, in the order of their base URLs.
+	pub fn inner(&self) -> &[C] {
+		&self.clients
+	}
+
+	/// Returns the base URLs currently deemed to be healthy, in order of preference.
+	pub fn healthy_base_urls(&self) -> Vec<&str> {
+		let state = self.state.read().unwrap();
+		let base_urls = self.base_urls.iter().zip(state.failed_since.iter());
+		let healthy = base_urls.filter(|(_, failed_since)| failed_since.is_none());
+		healthy.map(|(base_url, _)| base_url.as_str()).collect()
+	}
+
+	// Makes the given call against each base URL in turn, until one doesn't fail with a server
+	// failure.
+	async fn call<'a, T, Fut: Future<Output = Result<T, VssError>>, F: Fn(&'a C) -> Fut>(
+		&'a self, call: F,
+	) -> Result<T, VssError> {
+		let mut last_error = None;
+		for index in self.attempt_order() {
+			match call(&self.clients[index]).await {
+				Err(e) if e.is_server_failure() => {
+					self.on_failure(index);
+					last_error = Some(e);
+				},
+				result => {
+					self.on_success(index);
+					return result;
+				},
+			}
+		}
+		// unwrap safety: there's at least one base URL, each of which failed.
+		Err(last_error.unwrap())
+	}
+
+	// Orders the healthy base URLs and those due to be probed first, followed by the failed ones.
+	fn attempt_order(&self) -> Vec<usize> {
+		let state = self.state.read().unwrap();
+		let (mut preferred, failed): (Vec<usize>, Vec<usize>) =
+			(0..self.clients.len()).partition(|&index| match state.failed_since[index] {
+				None => true,
+				Some(since) => match self.recovery_policy {
+					RecoveryPolicy::Never => false,
+					RecoveryPolicy::ProbeAfter(interval) => since.elapsed() >= interval,
+				},
+			});
+		preferred.extend(failed);
+		preferred
+	}
+
+	fn on_success(&self, index: usize) {
+		if self.state.read().unwrap().failed_since[index].is_some() {
+			self.state.write().unwrap().failed_since[index] = None;
+		}
+	}
+
+	fn on_failure(&self, index: usize) {
+		self.state.write().unwrap().failed_since[index] = Some(Instant::now());
+	}
+}
+
+impl<C: VssApi> Clone for FailoverVssClient<C> {
+	fn clone(&self) -> Self {
+		Self {
+			base_urls: Arc::clone(&self.base_urls),
+			clients: Arc::clone(&self.clients),
+			recovery_policy: self.recovery_policy,
+			state: Arc::clone(&self.state),
+		}
+	}
+}
+
+#[async_trait]
+impl<C: VssApi> VssApi for FailoverVssClient<C> {
+	async fn get_object(&self, request: &GetObjectRequest) -> Result<GetObjectResponse, VssError> {
+		self.call(|client| client.get_object(request)).await
+	}
+
+	async fn put_object(&self, request: &PutObjectRequest) -> Result<PutObjectResponse, VssError> {
+		self.call(|client| client.put_object(request)).await
+	}
+
+	async fn delete_object(
+		&self, request: &DeleteObjectRequest,
+	) -> Result<DeleteObjectResponse, VssError> {
+		self.call(|client| client.delete_object(request)).await
+	}
+
+	async fn list_key_versions(
+		&self, request: &ListKeyVersionsRequest,
+	) -> Result<ListKeyVersionsResponse, VssError> {
+		self.call(|client| client.list_key_versions(request)).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_utils::{get_request, put_request, Fault, FlakyVssClient};
+	use std::sync::atomic::Ordering;
+
+	#[tokio::test]
+	async fn circuit_breaker_transitions() {
+		let open_duration = Duration::from_millis(50);
+		let flaky_client = FlakyVssClient::new();
+		flaky_client.fail_with(Some(Fault::ServerError));
+		let client = CircuitBreakerVssClient::new(flaky_client, 2, 2, open_duration);
+
+		// Failures below the threshold are passed through, other errors reset the count.
+		let result = client.put_object(&put_request("k1", -1, b"value")).await;
+		assert!(matches!(result, Err(VssError::InternalServerError { .. })));
+		client.inner().fail_with(None);
+		let result = client.get_object(&get_request("k1")).await;
+		assert!(matches!(result, Err(VssError::NoSuchKeyError { .. })));
+		client.inner().fail_with(Some(Fault::ServerError));
+		let result = client.put_object(&put_request("k1", -1, b"value")).await;
+		assert!(matches!(result, Err(VssError::InternalServerError { .. })));
+		assert_eq!(client.state(), CircuitState::Closed);
+
+		// Reaching the threshold opens the circuit, which fails fast for all operations.
+		let result = client.put_object(&put_request("k1", -1, b"value")).await;
+		assert!(matches!(result, Err(VssError::InternalServerError { .. })));
+		assert_eq!(client.state(), CircuitState::Open);
+		let result = client.get_object(&get_request("k1")).await;
+		assert!(matches!(result, Err(VssError::CircuitOpenError(..))));
+		assert_eq!(client.inner().calls.load(Ordering::SeqCst), 4);
+
+		// After the open duration calls are passed through, and a failure re-opens the circuit.
+		tokio::time::sleep(open_duration).await;
+		assert_eq!(client.state(), CircuitState::HalfOpen);
+		let result = client.get_object(&get_request("k1")).await;
+		assert!(matches!(result, Err(VssError::InternalServerError { .. })));
+		assert_eq!(client.state(), CircuitState::Open);
+
+		// Successive successes up to the threshold close the circuit again.
+		tokio::time::sleep(open_duration).await;
+		client.inner().fail_with(None);
+		client.put_object(&put_request("k1", -1, b"value")).await.unwrap();
+		assert_eq!(client.state(), CircuitState::HalfOpen);
+		client.get_object(&get_request("k1")).await.unwrap();
+		assert_eq!(client.state(), CircuitState::Closed);
+	}
+
+	#[tokio::test]
+	async fn fails_over_to_next_base_url() {
+		let base_urls =
+			vec!["https://vss-1.example".to_string(), "https://vss-2.example".to_string()];
+		let client =
+			FailoverVssClient::new(base_urls, RecoveryPolicy::Never, |_| Ok(FlakyVssClient::new()))
+				.unwrap();
+		let (primary, secondary) = (&client.inner()[0], &client.inner()[1]);
+
+		// Server failures fail over to the next base URL, which is used until it fails as well.
+		primary.fail_with(Some(Fault::ServerError));
+		client.put_object(&put_request("k1", -1, b"value")).await.unwrap();
+		assert_eq!(client.healthy_base_urls(), vec!["https://vss-2.example"]);
+		let result = client.get_object(&get_request("k2")).await;
+		assert!(matches!(result, Err(VssError::NoSuchKeyError { .. })));
+		assert_eq!(primary.calls.load(Ordering::SeqCst), 1);
+		assert_eq!(secondary.calls.load(Ordering::SeqCst), 2);
+
+		// With all base URLs failing, each is tried before giving up, re-promoting the one to
+		// recover first.
+		secondary.fail_with(Some(Fault::ServerError));
+		let result = client.put_object(&put_request("k1", -1, b"value")).await;
+		assert!(matches!(result, Err(VssError::InternalServerError { .. })));
+		assert!(client.healthy_base_urls().is_empty());
+		assert_eq!(primary.calls.load(Ordering::SeqCst), 2);
+		primary.fail_with(None);
+		client.clone().put_object(&put_request("k1", -1, b"value")).await.unwrap();
+		assert_eq!(client.healthy_base_urls(), vec!["https://vss-1.example"]);
+
+		let result =
+			FailoverVssClient::new(vec![], RecoveryPolicy::Never, |_| Ok(FlakyVssClient::new()));
+		assert!(matches!(result, Err(VssError::InvalidArgumentError(..))));
+	}
+
+	#[tokio::test]
+	async fn rate_limiting_does_not_fail_over() {
+		let base_urls =
+			vec!["https://vss-1.example".to_string(), "https://vss-2.example".to_string()];
+		let client =
+			FailoverVssClient::new(base_urls, RecoveryPolicy::Never, |_| Ok(FlakyVssClient::new()))
+				.unwrap();
+		let (primary, secondary) = (&client.inner()[0], &client.inner()[1]);
+
+		// A rate limited replica is available, so the error is returned right away.
+		primary.fail_with(Some(Fault::RateLimited));
+		let result = client.put_object(&put_request("k1", -1, b"value")).await;
+		assert!(matches!(result, Err(VssError::RateLimitedError { .. })));
+		assert_eq!(client.healthy_base_urls().len(), 2);
+		assert_eq!(secondary.calls.load(Ordering::SeqCst), 0);
+	}
+
+	#[tokio::test]
+	async fn probes_failed_base_urls() {
+		let probe_interval = Duration::from_millis(50);
+		let base_urls =
+			vec!["https://vss-1.example".to_string(), "https://vss-2.example".to_string()];
+		let recovery_policy = RecoveryPolicy::ProbeAfter(probe_interval);
+		let client =
+			FailoverVssClient::new(base_urls, recovery_policy, |_| Ok(FlakyVssClient::new()))
+				.unwrap();
+		let (primary, secondary) = (&client.inner()[0], &client.inner()[1]);
+
+		primary.fail_with(Some(Fault::ServerError));
+		client.put_object(&put_request("k1", -1, b"value")).await.unwrap();
+		primary.fail_with(None);
+		client.put_object(&put_request("k1", -1, b"value")).await.unwrap();
+		assert_eq!(primary.calls.load(Ordering::SeqCst), 1);
+
+		// After the interval, the primary is probed and re-promoted once it succeeds.
+		tokio::time::sleep(probe_interval).await;
+		client.put_object(&put_request("k1", -1, b"value")).await.unwrap();
+		assert_eq!(primary.calls.load(Ordering::SeqCst), 2);
+		assert_eq!(secondary.calls.load(Ordering::SeqCst), 2);
+		assert_eq!(client.healthy_base_urls().len(), 2);
+	}
+}
@@ -0,0 +1,366 @@
+use async_trait::async_trait;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+use crate::client::VssApi;
+use crate::error::VssError;
+use crate::types::{
+	DeleteObjectRequest, DeleteObjectResponse, GetObjectRequest, GetObjectResponse, KeyValue,
+	ListKeyVersionsRequest, ListKeyVersionsResponse, PutObjectRequest, PutObjectResponse,
+};
+#[cfg(test)]
+use crate::util::storable_builder::EntropySource;
+#[cfg(test)]
+use rand::RngCore;
+#[cfg(test)]
+use std::sync::atomic::{AtomicU32, Ordering};
+
+// The version which skips key-level version checks, i.e. results in a non-conditional write/delete.
+const NON_CONDITIONAL_VERSION: i64 = -1;
+
+#[derive(Default)]
+struct Store {
+	global_version: i64,
+	items: BTreeMap<String, KeyValue>,
+}
+
+/// An in-memory implementation of [`VssApi`], emulating the semantics of a VSS server.
+///
+/// Useful for writing deterministic tests of code depending on [`VssApi`], without requiring a VSS
+/// server to be running. This includes key-level and global versioning, atomic transactions as
+/// well as listing keys by prefix across pages.
+#[derive(Default)]
+pub struct InMemoryVssClient {
+	stores: Mutex<HashMap<String, Store>>,
+}
+
+impl InMemoryVssClient {
+	/// Constructs a new, empty instance.
+	pub fn new() -> Self {
+		Self::default()
+	}
+}
+
+#[async_trait]
+impl VssApi for InMemoryVssClient {
+	async fn get_object(&self, request: &GetObjectRequest) -> Result<GetObjectResponse, VssError> {
+		let stores = self.stores.lock().unwrap();
+		let value = stores.get(&request.store_id).and_then(|store| store.items.get(&request.key));
+		match value {
+			Some(value) => Ok(GetObjectResponse { value: Some(value.clone()) }),
+			None => Err(VssError::NoSuchKeyError {
+				message: format!("Requested key not found: {}", request.key),
+				store_id: Some(request.store_id.clone()),
+				key: Some(request.key.clone()),
+				request_id: None,
+			}),
+		}
+	}
+
+	async fn put_object(&self, request: &PutObjectRequest) -> Result<PutObjectResponse, VssError> {
+		let mut stores = self.stores.lock().unwrap();
+		let store = stores.entry(request.store_id.clone()).or_default();
+
+		if let Some(global_version) = request.global_version {
+			if global_version != store.global_version {
+				return Err(VssError::ConflictError {
+					message: format!(
+						"Global version mismatch, expected: {}, found: {}",
+						store.global_version, global_version
+					),
+					key: None,
+					expected_version: None,
+					current_version: None,
+					request_id: None,
+				});
+			}
+		}
+		for item in &request.transaction_items {
+			let current_version = store.items.get(&item.key).map_or(0, |kv| kv.version);
+			if item.version != NON_CONDITIONAL_VERSION && item.version != current_version {
+				return Err(VssError::ConflictError {
+					message: format!(
+						"Version mismatch for key: {}, expected: {}, found: {}",
+						item.key, current_version, item.version
+					),
+					key: Some(item.key.clone()),
+					expected_version: Some(item.version),
+					current_version: Some(current_version),
+					request_id: None,
+				});
+			}
+		}
+		for item in &request.delete_items {
+			match store.items.get(&item.key) {
+				Some(kv)
+					if item.version == NON_CONDITIONAL_VERSION || item.version == kv.version => {},
+				kv => {
+					return Err(VssError::ConflictError {
+						message: format!(
+							"Key does not exist or version mismatch for key: {}",
+							item.key
+						),
+						key: Some(item.key.clone()),
+						expected_version: Some(item.version),
+						current_version: kv.map(|kv| kv.version),
+						request_id: None,
+					});
+				},
+			}
+		}
+
+		for item in &request.transaction_items {
+			let version =
+				if item.version == NON_CONDITIONAL_VERSION { 1 } else { item.version + 1 };
+			store.items.insert(
+				item.key.clone(),
+				KeyValue { key: item.key.clone(), version, value: item.value.clone() },
+			);
+		}
+		for item in &request.delete_items {
+			store.items.remove(&item.key);
+		}
+		store.global_version += 1;
+		Ok(PutObjectResponse {})
+	}
+
+	async fn delete_object(
+		&self, request: &DeleteObjectRequest,
+	) -> Result<DeleteObjectResponse, VssError> {
+		let key_value = request.key_value.as_ref().ok_or_else(|| {
+			VssError::InvalidRequestError("key_value missing in DeleteObjectRequest".to_string())
+		})?;
+		let mut stores = self.stores.lock().unwrap();
+		if let Some(store) = stores.get_mut(&request.store_id) {
+			let matches_version = store.items.get(&key_value.key).map_or(false, |kv| {
+				key_value.version == NON_CONDITIONAL_VERSION || key_value.version == kv.version
+			});
+			if matches_version {
+				store.items.remove(&key_value.key);
+			}
+		}
+		Ok(DeleteObjectResponse {})
+	}
+
+	async fn list_key_versions(
+		&self, request: &ListKeyVersionsRequest,
+	) -> Result<ListKeyVersionsResponse, VssError> {
+		let stores = self.stores.lock().unwrap();
+		let store = stores.get(&request.store_id);
+
+		let key_prefix = request.key_prefix.as_deref().unwrap_or("");
+		let page_size = match request.page_size {
+			Some(page_size) if page_size > 0 => page_size as usize,
+			_ => usize::MAX,
+		};
+		let key_versions: Vec<KeyValue> = store
+			.into_iter()
+			.flat_map(|store| store.items.values())
+			.filter(|kv| kv.key.starts_with(key_prefix))
+			.filter(|kv| request.page_token.as_ref().map_or(true, |token| kv.key > *token))
+			.take(page_size)
+			.map(|kv| KeyValue { key: kv.key.clone(), version: kv.version, value: vec![] })
+			.collect();
+
+		let next_page_token = key_versions.last().map(|kv| kv.key.clone()).or(Some(String::new()));
+		// The global version is only returned as part of the first page.
+		let global_version = if request.page_token.is_none() {
+			Some(store.map_or(0, |store| store.global_version))
+		} else {
+			None
+		};
+		Ok(ListKeyVersionsResponse { key_versions, next_page_token, global_version })
+	}
+}
+
+// Builds a request writing `value` against `key` in the store `store`, for use in unit tests.
+#[cfg(test)]
+pub(crate) fn put_request(key: &str, version: i64, value: &[u8]) -> PutObjectRequest {
+	PutObjectRequest {
+		store_id: "store".to_string(),
+		global_version: None,
+		transaction_items: vec![KeyValue { key: key.to_string(), version, value: value.to_vec() }],
+		delete_items: vec![],
+	}
+}
+
+// Builds a request fetching `key` from the store `store`, for use in unit tests.
+#[cfg(test)]
+pub(crate) fn get_request(key: &str) -> GetObjectRequest {
+	GetObjectRequest { store_id: "store".to_string(), key: key.to_string() }
+}
+
+// An `EntropySource` drawing from the thread-local random number generator, for use in unit tests.
+#[cfg(test)]
+pub(crate) struct RandomEntropySource;
+
+#[cfg(test)]
+impl EntropySource for RandomEntropySource {
+	fn fill_bytes(&self, buffer: &mut [u8]) {
+		rand::thread_rng().fill_bytes(buffer);
+	}
+}
+
+// A fault injected by a `FlakyVssClient`.
+#[cfg(test)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Fault {
+	ServerError,
+	RateLimited,
+	Timeout,
+}
+
+#[cfg(test)]
+impl Fault {
+	fn error(self) -> VssError {
+		match self {
+			Fault::ServerError => VssError::InternalServerError {
+				message: "Unavailable".to_string(),
+				status: None,
+				request_id: None,
+			},
+			Fault::RateLimited => VssError::RateLimitedError {
+				message: "Too many requests".to_string(),
+				retry_after: None,
+				status: Some(429),
+				request_id: None,
+			},
+			Fault::Timeout => VssError::TimeoutError {
+				message: "Request timed out".to_string(),
+				source: None,
+				request_id: None,
+			},
+		}
+	}
+}
+
+// Wraps an `InMemoryVssClient`, failing all calls with the error of the injected `Fault`, if any,
+// for use in unit tests.
+#[cfg(test)]
+pub(crate) struct FlakyVssClient {
+	pub(crate) inner: InMemoryVssClient,
+	fault: Mutex<Option<Fault>>,
+	pub(crate) calls: AtomicU32,
+}
+
+#[cfg(test)]
+impl FlakyVssClient {
+	pub(crate) fn new() -> Self {
+		Self { inner: InMemoryVssClient::new(), fault: Mutex::new(None), calls: AtomicU32::new(0) }
+	}
+
+	// Injects `fault` into all subsequent calls, or stops failing them if `None`.
+	pub(crate) fn fail_with(&self, fault: Option<Fault>) {
+		*self.fault.lock().unwrap() = fault;
+	}
+
+	fn check(&self) -> Result<(), VssError> {
+		self.calls.fetch_add(1, Ordering::SeqCst);
+		match *self.fault.lock().unwrap() {
+			Some(fault) => Err(fault.error()),
+			None => Ok(()),
+		}
+	}
+}
+
+#[cfg(test)]
+#[async_trait]
+impl VssApi for FlakyVssClient {
+	async fn get_object(&self, request: &GetObjectRequest) -> Result<GetObjectResponse, VssError> {
+		self.check()?;
+		self.inner.get_object(request).await
+	}
+
+	async fn put_object(&self, request: &PutObjectRequest) -> Result<PutObjectResponse, VssError> {
+		self.check()?;
+		self.inner.put_object(request).await
+	}
+
+	async fn delete_object(
+		&self, request: &DeleteObjectRequest,
+	) -> Result<DeleteObjectResponse, VssError> {
+		self.check()?;
+		self.inner.delete_object(request).await
+	}
+
+	async fn list_key_versions(
+		&self, request: &ListKeyVersionsRequest,
+	) -> Result<ListKeyVersionsResponse, VssError> {
+		self.check()?;
+		self.inner.list_key_versions(request).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn put_get_delete() {
+		let client = InMemoryVssClient::new();
+		assert!(matches!(
+			client.get_object(&get_request("k1")).await,
+			Err(VssError::NoSuchKeyError { .. })
+		));
+
+		client.put_object(&put_request("k1", 0, b"v1")).await.unwrap();
+		let value = client.get_object(&get_request("k1")).await.unwrap().value.unwrap();
+		assert_eq!(value, KeyValue { key: "k1".to_string(), version: 1, value: b"v1".to_vec() });
+
+		// Writing with a stale version results in a conflict.
+		assert!(matches!(
+			client.put_object(&put_request("k1", 0, b"v2")).await,
+			Err(VssError::ConflictError {
+				key: Some(key),
+				expected_version: Some(0),
+				current_version: Some(1),
+				..
+			}) if key == "k1"
+		));
+		client.put_object(&put_request("k1", 1, b"v2")).await.unwrap();
+		let value = client.get_object(&get_request("k1")).await.unwrap().value.unwrap();
+		assert_eq!(value.version, 2);
+
+		let delete_request = DeleteObjectRequest {
+			store_id: "store".to_string(),
+			key_value: Some(KeyValue { key: "k1".to_string(), version: 2, value: vec![] }),
+		};
+		client.delete_object(&delete_request).await.unwrap();
+		// Deletes are idempotent.
+		client.delete_object(&delete_request).await.unwrap();
+		assert!(matches!(
+			client.get_object(&get_request("k1")).await,
+			Err(VssError::NoSuchKeyError { .. })
+		));
+	}
+
+	#[tokio::test]
+	async fn list_key_versions_paginates() {
+		let client = InMemoryVssClient::new();
+		for key in ["a1", "b1", "b2", "b3"] {
+			client.put_object(&put_request(key, 0, b"value")).await.unwrap();
+		}
+
+		let mut request = ListKeyVersionsRequest {
+			store_id: "store".to_string(),
+			key_prefix: Some("b".to_string()),
+			page_size: Some(2),
+			page_token: None,
+		};
+		let first_page = client.list_key_versions(&request).await.unwrap();
+		let keys: Vec<&str> = first_page.key_versions.iter().map(|kv| kv.key.as_str()).collect();
+		assert_eq!(keys, vec!["b1", "b2"]);
+		assert_eq!(first_page.global_version, Some(4));
+
+		request.page_token = first_page.next_page_token;
+		let second_page = client.list_key_versions(&request).await.unwrap();
+		let keys: Vec<&str> = second_page.key_versions.iter().map(|kv| kv.key.as_str()).collect();
+		assert_eq!(keys, vec!["b3"]);
+		assert_eq!(second_page.global_version, None);
+
+		request.page_token = second_page.next_page_token;
+		let last_page = client.list_key_versions(&request).await.unwrap();
+		assert!(last_page.key_versions.is_empty());
+		assert_eq!(last_page.next_page_token, Some(String::new()));
+	}
+}
@@ -0,0 +1,190 @@
+use std::collections::HashSet;
+
+use crate::client::VssApi;
+use crate::error::VssError;
+use crate::types::{KeyValue, PutObjectRequest, PutObjectResponse};
+
+/// A builder for an atomic transaction of writes and deletes within a single store, sent as a
+/// [`PutObjectRequest`].
+///
+/// All writes and deletes of a transaction are applied all-or-nothing by the VSS server, each being
+/// conditioned on the given version of its key, as well as on the global version of the store if
+/// set via [`VssTransaction::global_version`].
+///
+/// The transaction is validated on [`VssTransaction::build`] and [`VssTransaction::commit`],
+/// before anything is sent: besides the store id and keys being non-empty, a key must not be
+/// written or deleted more than once, and all versions must be non-negative, as a transaction is
+/// meant to be conditioned on the versions last read.
+///
+/// **Example**
+/// ```rust
+/// # use vss_client::transaction::VssTransaction;
+/// let request = VssTransaction::for_store("store".to_string())
+/// 	.put("k1".to_string(), 2, b"k1v3".to_vec())
+/// 	.delete("k2".to_string(), 5)
+/// 	.global_version(7)
+/// 	.build()
+/// 	.unwrap();
+/// assert_eq!(request.transaction_items.len(), 1);
+/// assert_eq!(request.delete_items.len(), 1);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct VssTransaction {
+	store_id: String,
+	global_version: Option<i64>,
+	transaction_items: Vec<KeyValue>,
+	delete_items: Vec<KeyValue>,
+}
+
+impl VssTransaction {
+	/// Constructs a new, empty transaction within the given `store_id`.
+	pub fn for_store(store_id: String) -> Self {
+		Self {
+			store_id,
+			global_version: None,
+			transaction_items: Vec::new(),
+			delete_items: Vec::new(),
+		}
+	}
+
+	/// Writes the given `value` against the given `key`, conditioned on `version` being its current
+	/// version, i.e. `0` for its first write, see [`KeyValue::version`].
+	pub fn put(mut self, key: String, version: i64, value: Vec<u8>) -> Self {
+		self.transaction_items.push(KeyValue { key, version, value });
+		self
+	}
+
+	/// Deletes the given `key`, conditioned on `version` being its current version.
+	pub fn delete(mut self, key: String, version: i64) -> Self {
+		self.delete_items.push(KeyValue { key, version, value: Vec::new() });
+		self
+	}
+
+	/// Conditions the transaction on `global_version` being the current global version of the
+	/// store, see [`PutObjectRequest::global_version`].
+	pub fn global_version(mut self, global_version: i64) -> Self {
+		self.global_version = Some(global_version);
+		self
+	}
+
+	/// Validates the transaction, returning it as [`PutObjectRequest`].
+	///
+	/// Returns [`VssError::InvalidRequestError`] if the transaction is invalid, see
+	/// [`VssTransaction`].
+	pub fn build(self) -> Result<PutObjectRequest, VssError> {
+		self.validate()?;
+		Ok(PutObjectRequest {
+			store_id: self.store_id,
+			global_version: self.global_version,
+			transaction_items: self.transaction_items,
+			delete_items: self.delete_items,
+		})
+	}
+
+	/// Validates the transaction like [`VssTransaction::build`], and submits it via the given
+	/// `client`.
+	pub async fn commit<C: VssApi>(self, client: &C) -> Result<PutObjectResponse, VssError> {
+		let request = self.build()?;
+		client.put_object(&request).await
+	}
+
+	fn validate(&self) -> Result<(), VssError> {
+		let invalid = |reason: String| {
+			Err(VssError::InvalidRequestError(format!("Invalid transaction: {}", reason)))
+		};
+		if self.store_id.is_empty() {
+			return invalid("store_id must not be empty".to_string());
+		}
+		if self.transaction_items.is_empty() && self.delete_items.is_empty() {
+			return invalid("contains neither writes nor deletes".to_string());
+		}
+		match self.global_version {
+			Some(global_version) if global_version < 0 => {
+				return invalid(format!(
+					"global_version must be non-negative, found: {}",
+					global_version
+				));
+			},
+			_ => {},
+		}
+		let mut keys = HashSet::new();
+		for item in self.transaction_items.iter().chain(self.delete_items.iter()) {
+			if item.key.is_empty() {
+				return invalid("key must not be empty".to_string());
+			}
+			if item.version < 0 {
+				return invalid(format!(
+					"version of key: {} must be non-negative, found: {}",
+					item.key, item.version
+				));
+			}
+			if !keys.insert(item.key.as_str()) {
+				return invalid(format!("key: {} is written or deleted more than once", item.key));
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn assert_invalid(transaction: VssTransaction) {
+		let result = transaction.build();
+		assert!(matches!(result, Err(VssError::InvalidRequestError(..))), "{:?}", result);
+	}
+
+	fn transaction() -> VssTransaction {
+		VssTransaction::for_store("store".to_string())
+	}
+
+	#[test]
+	fn builds_put_object_request() {
+		let request = transaction()
+			.put("k1".to_string(), 0, b"k1v1".to_vec())
+			.put("k2".to_string(), 3, b"k2v4".to_vec())
+			.delete("k3".to_string(), 1)
+			.global_version(7)
+			.build()
+			.unwrap();
+		assert_eq!(
+			request,
+			PutObjectRequest {
+				store_id: "store".to_string(),
+				global_version: Some(7),
+				transaction_items: vec![
+					KeyValue { key: "k1".to_string(), version: 0, value: b"k1v1".to_vec() },
+					KeyValue { key: "k2".to_string(), version: 3, value: b"k2v4".to_vec() },
+				],
+				delete_items: vec![KeyValue { key: "k3".to_string(), version: 1, value: vec![] }],
+			}
+		);
+	}
+
+	#[test]
+	fn rejects_duplicate_keys() {
+		assert_invalid(transaction().put("k1".to_string(), 0, vec![]).put(
+			"k1".to_string(),
+			1,
+			vec![],
+		));
+		assert_invalid(transaction().delete("k1".to_string(), 1).delete("k1".to_string(), 1));
+		assert_invalid(transaction().put("k1".to_string(), 1, vec![]).delete("k1".to_string(), 1));
+	}
+
+	#[test]
+	fn rejects_negative_versions() {
+		assert_invalid(transaction().put("k1".to_string(), -1, vec![]));
+		assert_invalid(transaction().delete("k1".to_string(), -1));
+		assert_invalid(transaction().put("k1".to_string(), 0, vec![]).global_version(-1));
+	}
+
+	#[test]
+	fn rejects_empty_transactions() {
+		assert_invalid(transaction());
+		assert_invalid(transaction().global_version(1));
+		assert_invalid(VssTransaction::for_store(String::new()).put("k1".to_string(), 0, vec![]));
+		assert_invalid(transaction().put(String::new(), 0, vec![]));
+	}
+}
@@ -0,0 +1,212 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::marker::PhantomData;
+
+use crate::client::VssApi;
+use crate::error::VssError;
+use crate::types::{DeleteObjectRequest, GetObjectRequest, KeyValue, PutObjectRequest};
+
+/// Defines how keys and values of type `T` are serialized by a [`TypedVssClient`].
+pub trait Codec<T>: Send + Sync {
+	/// Serializes the given `value`.
+	fn encode(&self, value: &T) -> Result<Vec<u8>, VssError>;
+
+	/// Deserializes a value from the given `bytes`.
+	fn decode(&self, bytes: &[u8]) -> Result<T, VssError>;
+}
+
+/// A [`Codec`] serializing keys and values as JSON.
+///
+/// Note that this results in keys of type `String` being stored including their quotes.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct JsonCodec;
+
+impl<T: Serialize + DeserializeOwned> Codec<T> for JsonCodec {
+	fn encode(&self, value: &T) -> Result<Vec<u8>, VssError> {
+		serde_json::to_vec(value)
+			.map_err(|e| VssError::SerializationError(format!("Failed to encode JSON: {}", e)))
+	}
+
+	fn decode(&self, bytes: &[u8]) -> Result<T, VssError> {
+		serde_json::from_slice(bytes)
+			.map_err(|e| VssError::SerializationError(format!("Failed to decode JSON: {}", e)))
+	}
+}
+
+/// A [`Codec`] passing through raw bytes, i.e. not serializing them at all.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RawCodec;
+
+impl Codec<Vec<u8>> for RawCodec {
+	fn encode(&self, value: &Vec<u8>) -> Result<Vec<u8>, VssError> {
+		Ok(value.clone())
+	}
+
+	fn decode(&self, bytes: &[u8]) -> Result<Vec<u8>, VssError> {
+		Ok(bytes.to_vec())
+	}
+}
+
+/// A wrapper around a [`VssApi`] implementation, providing access to keys of type `K` and values
+/// of type `V`, serialized via the given [`Codec`].
+///
+/// As VSS keys are strings, serialized keys need to be valid UTF-8, otherwise a
+/// [`VssError::SerializationError`] is returned. Values are stored using the key-level
+/// versioning of VSS, see [`KeyValue::version`].
+pub struct TypedVssClient<K, V, C: VssApi, D = JsonCodec> {
+	inner: C,
+	codec: D,
+	_types: PhantomData<fn() -> (K, V)>,
+}
+
+impl<K, V, C, D> TypedVssClient<K, V, C, D>
+where
+	K: Serialize + DeserializeOwned,
+	V: Serialize + DeserializeOwned,
+	C: VssApi,
+	D: Codec<K> + Codec<V>,
+{
+	/// Constructs a new instance wrapping `inner`, serializing keys and values using `codec`.
+	pub fn new(inner: C, codec: D) -> Self {
+		Self { inner, codec, _types: PhantomData }
+	}
+
+	/// Returns the wrapped [`VssApi`] implementation.
+	pub fn inner(&self) -> &C {
+		&self.inner
+	}
+
+	/// Fetches the value against the given `key` in the given `store_id`.
+	///
+	/// Returns [`VssError::NoSuchKeyError`] if the key doesn't exist.
+	pub async fn get(&self, store_id: &str, key: &K) -> Result<V, VssError> {
+		let request =
+			GetObjectRequest { store_id: store_id.to_string(), key: self.encode_key(key)? };
+		let response = self.inner.get_object(&request).await?;
+		match response.value {
+			Some(key_value) => Codec::<V>::decode(&self.codec, &key_value.value),
+			None => Err(VssError::NoSuchKeyError {
+				message: format!("Requested key not found: {}", request.key),
+				store_id: Some(request.store_id.clone()),
+				key: Some(request.key.clone()),
+				request_id: None,
+			}),
+		}
+	}
+
+	/// Fetches the value against the given `key` in the given `store_id`, returning
+	/// `default_value` if the key doesn't exist.
+	///
+	/// Only a [`VssError::NoSuchKeyError`] is suppressed, while all other errors are returned.
+	pub async fn get_or_default(
+		&self, store_id: &str, key: &K, default_value: V,
+	) -> Result<V, VssError> {
+		match self.get(store_id, key).await {
+			Err(VssError::NoSuchKeyError { .. }) => Ok(default_value),
+			result => result,
+		}
+	}
+
+	/// Writes the given `value` against the given `key` in the given `store_id`.
+	///
+	/// The `version` is expected to be the version of the key as last read, i.e. `0` for its first
+	/// write, or `-1` for a non-conditional write, see [`KeyValue::version`].
+	pub async fn put(
+		&self, store_id: &str, key: &K, value: &V, version: i64,
+	) -> Result<(), VssError> {
+		let request = PutObjectRequest {
+			store_id: store_id.to_string(),
+			global_version: None,
+			transaction_items: vec![KeyValue {
+				key: self.encode_key(key)?,
+				version,
+				value: Codec::<V>::encode(&self.codec, value)?,
+			}],
+			delete_items: vec![],
+		};
+		self.inner.put_object(&request).await.map(|_| ())
+	}
+
+	/// Deletes the given `key` in the given `store_id`, conditioned on its `version` unless set to
+	/// `-1`.
+	pub async fn delete(&self, store_id: &str, key: &K, version: i64) -> Result<(), VssError> {
+		let request = DeleteObjectRequest {
+			store_id: store_id.to_string(),
+			key_value: Some(KeyValue { key: self.encode_key(key)?, version, value: vec![] }),
+		};
+		self.inner.delete_object(&request).await.map(|_| ())
+	}
+
+	fn encode_key(&self, key: &K) -> Result<String, VssError> {
+		let key_bytes = Codec::<K>::encode(&self.codec, key)?;
+		String::from_utf8(key_bytes).map_err(|e| {
+			VssError::SerializationError(format!("Serialized key is not valid UTF-8: {}", e))
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_utils::InMemoryVssClient;
+	use serde::Deserialize;
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	struct ChannelId(u64);
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	struct ChannelState {
+		balance_msat: u64,
+		is_open: bool,
+	}
+
+	#[tokio::test]
+	async fn puts_gets_and_deletes_typed_values() {
+		let client: TypedVssClient<ChannelId, ChannelState, _> =
+			TypedVssClient::new(InMemoryVssClient::new(), JsonCodec);
+		let state = ChannelState { balance_msat: 42_000, is_open: true };
+		client.put("store", &ChannelId(1), &state, 0).await.unwrap();
+		assert_eq!(client.get("store", &ChannelId(1)).await.unwrap(), state);
+
+		// Values are stored serialized, using the key-level versioning of VSS.
+		let request = GetObjectRequest { store_id: "store".to_string(), key: "1".to_string() };
+		let stored = client.inner().get_object(&request).await.unwrap().value.unwrap();
+		assert_eq!(stored.value, br#"{"balance_msat":42000,"is_open":true}"#.to_vec());
+		assert!(matches!(
+			client.put("store", &ChannelId(1), &state, 0).await,
+			Err(VssError::ConflictError { .. })
+		));
+
+		client.delete("store", &ChannelId(1), 1).await.unwrap();
+		assert!(matches!(
+			client.get("store", &ChannelId(1)).await,
+			Err(VssError::NoSuchKeyError { .. })
+		));
+		let default_state = ChannelState { balance_msat: 0, is_open: false };
+		let state = client.get_or_default("store", &ChannelId(1), default_state).await.unwrap();
+		assert_eq!(state, ChannelState { balance_msat: 0, is_open: false });
+	}
+
+	#[tokio::test]
+	async fn surfaces_serialization_errors() {
+		let raw_client: TypedVssClient<Vec<u8>, Vec<u8>, _, _> =
+			TypedVssClient::new(InMemoryVssClient::new(), RawCodec);
+		raw_client.put("store", &b"1".to_vec(), &b"not json".to_vec(), 0).await.unwrap();
+		assert!(matches!(
+			raw_client.put("store", &vec![0xff], &vec![], 0).await,
+			Err(VssError::SerializationError(..))
+		));
+
+		let client: TypedVssClient<ChannelId, ChannelState, _> =
+			TypedVssClient::new(raw_client.inner, JsonCodec);
+		assert!(matches!(
+			client.get("store", &ChannelId(1)).await,
+			Err(VssError::SerializationError(..))
+		));
+		let default_state = ChannelState { balance_msat: 0, is_open: false };
+		assert!(matches!(
+			client.get_or_default("store", &ChannelId(1), default_state).await,
+			Err(VssError::SerializationError(..))
+		));
+	}
+}
@@ -1,5 +1,7 @@
 /// Request payload to be used for `GetObject` API call to server.
 #[allow(clippy::derive_partial_eq_without_eq)]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "camelCase", default))]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetObjectRequest {
 	/// `store_id` is a keyspace identifier.
@@ -27,14 +29,19 @@ pub struct GetObjectRequest {
 }
 /// Server response for `GetObject` API.
 #[allow(clippy::derive_partial_eq_without_eq)]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "camelCase", default))]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetObjectResponse {
 	/// Fetched `value` and `version` along with the corresponding `key` in the request.
 	#[prost(message, optional, tag = "2")]
+	#[cfg_attr(feature = "json", serde(skip_serializing_if = "Option::is_none"))]
 	pub value: ::core::option::Option<KeyValue>,
 }
 /// Request payload to be used for `PutObject` API call to server.
 #[allow(clippy::derive_partial_eq_without_eq)]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "camelCase", default))]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PutObjectRequest {
 	/// `store_id` is a keyspace identifier.
@@ -63,6 +70,10 @@ pub struct PutObjectRequest {
 	///
 	/// Requests with a conflicting version will fail with `CONFLICT_EXCEPTION` as ErrorCode.
 	#[prost(int64, optional, tag = "2")]
+	#[cfg_attr(
+		feature = "json",
+		serde(with = "crate::json::optional_int64", skip_serializing_if = "Option::is_none")
+	)]
 	pub global_version: ::core::option::Option<i64>,
 	/// Items to be written as a result of this `PutObjectRequest`.
 	///
@@ -134,10 +145,14 @@ pub struct PutObjectRequest {
 }
 /// Server response for `PutObject` API.
 #[allow(clippy::derive_partial_eq_without_eq)]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "camelCase", default))]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PutObjectResponse {}
 /// Request payload to be used for `DeleteObject` API call to server.
 #[allow(clippy::derive_partial_eq_without_eq)]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "camelCase", default))]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct DeleteObjectRequest {
 	/// `store_id` is a keyspace identifier.
@@ -165,14 +180,19 @@ pub struct DeleteObjectRequest {
 	/// If the requested item does not exist, this operation will not fail.
 	/// If you wish to perform stricter checks while deleting an item, consider using `PutObject` API.
 	#[prost(message, optional, tag = "2")]
+	#[cfg_attr(feature = "json", serde(skip_serializing_if = "Option::is_none"))]
 	pub key_value: ::core::option::Option<KeyValue>,
 }
 /// Server response for `DeleteObject` API.
 #[allow(clippy::derive_partial_eq_without_eq)]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "camelCase", default))]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct DeleteObjectResponse {}
 /// Request payload to be used for `ListKeyVersions` API call to server.
 #[allow(clippy::derive_partial_eq_without_eq)]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "camelCase", default))]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ListKeyVersionsRequest {
 	/// `store_id` is a keyspace identifier.
@@ -192,12 +212,14 @@ pub struct ListKeyVersionsRequest {
 	/// If no `key_prefix` is specified or it is empty (""), all the keys are eligible to be returned in
 	/// the response.
 	#[prost(string, optional, tag = "2")]
+	#[cfg_attr(feature = "json", serde(skip_serializing_if = "Option::is_none"))]
 	pub key_prefix: ::core::option::Option<::prost::alloc::string::String>,
 	/// `page_size` is used by clients to specify the maximum number of results that can be returned by
 	/// the server.
 	/// The server may further constrain the maximum number of results returned in a single page.
 	/// If the `page_size` is 0 or not set, the server will decide the number of results to be returned.
 	#[prost(int32, optional, tag = "3")]
+	#[cfg_attr(feature = "json", serde(skip_serializing_if = "Option::is_none"))]
 	pub page_size: ::core::option::Option<i32>,
 	/// `page_token` is a pagination token.
 	///
@@ -206,10 +228,13 @@ pub struct ListKeyVersionsRequest {
 	/// For subsequent pages, use the value that was returned as `next_page_token` in the previous
 	/// page's `ListKeyVersionsResponse`.
 	#[prost(string, optional, tag = "4")]
+	#[cfg_attr(feature = "json", serde(skip_serializing_if = "Option::is_none"))]
 	pub page_token: ::core::option::Option<::prost::alloc::string::String>,
 }
 /// Server response for `ListKeyVersions` API.
 #[allow(clippy::derive_partial_eq_without_eq)]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "camelCase", default))]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ListKeyVersionsResponse {
 	/// Fetched keys and versions.
@@ -230,6 +255,7 @@ pub struct ListKeyVersionsResponse {
 	/// Caution: Clients must not assume a specific number of key_versions to be present in a page for
 	/// paginated response.
 	#[prost(string, optional, tag = "2")]
+	#[cfg_attr(feature = "json", serde(skip_serializing_if = "Option::is_none"))]
 	pub next_page_token: ::core::option::Option<::prost::alloc::string::String>,
 	/// `global_version` is a sequence-number/version of the whole store.
 	///
@@ -245,17 +271,24 @@ pub struct ListKeyVersionsResponse {
 	/// This guarantee is helpful for ensuring the versioning correctness if using the `global_version`
 	/// in `PutObject` API and can help avoid the race conditions related to it.
 	#[prost(int64, optional, tag = "3")]
+	#[cfg_attr(
+		feature = "json",
+		serde(with = "crate::json::optional_int64", skip_serializing_if = "Option::is_none")
+	)]
 	pub global_version: ::core::option::Option<i64>,
 }
 /// When HttpStatusCode is not ok (200), the response `content` contains a serialized `ErrorResponse`
 /// with the relevant `ErrorCode` and `message`
 #[allow(clippy::derive_partial_eq_without_eq)]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "camelCase", default))]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ErrorResponse {
 	/// The error code uniquely identifying an error condition.
 	/// It is meant to be read and understood programmatically by code that detects/handles errors by
 	/// type.
 	#[prost(enumeration = "ErrorCode", tag = "1")]
+	#[cfg_attr(feature = "json", serde(with = "crate::json::error_code"))]
 	pub error_code: i32,
 	/// The error message containing a generic description of the error condition in English.
 	/// It is intended for a human audience only and should not be parsed to extract any information
@@ -265,6 +298,8 @@ pub struct ErrorResponse {
 }
 /// Represents a key-value pair to be stored or retrieved.
 #[allow(clippy::derive_partial_eq_without_eq)]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "camelCase", default))]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct KeyValue {
 	/// Key against which the value is stored.
@@ -277,12 +312,14 @@ pub struct KeyValue {
 	/// client-side increment is required to ensure matching versions. These updated key versions should
 	/// be used in subsequent `PutObjectRequest`s for the keys.
 	#[prost(int64, tag = "2")]
+	#[cfg_attr(feature = "json", serde(with = "crate::json::int64"))]
 	pub version: i64,
 	/// Object value in bytes which is stored (in put) and fetched (in get).
 	/// Clients must encrypt the secret contents of this blob client-side before sending it over the
 	/// wire to the server in order to preserve privacy and security.
 	/// Clients may use a `Storable` object, serialize it and set it here.
 	#[prost(bytes = "vec", tag = "3")]
+	#[cfg_attr(feature = "json", serde(with = "crate::json::bytes"))]
 	pub value: ::prost::alloc::vec::Vec<u8>,
 }
 /// Represents a storable object that can be serialized and stored as `value` in `PutObjectRequest`.
@@ -0,0 +1,114 @@
+use bitcoin_hashes::{sha256, Hash};
+
+use crate::error::VssError;
+
+// Identifies manifests of values stored in chunks, see `VssClient::put_chunked`.
+const MANIFEST_MAGIC: &[u8; 8] = b"VSSCHNK\x01";
+// The magic, followed by the chunk count, the total length and the SHA-256 hash of the value.
+const MANIFEST_LENGTH: usize = MANIFEST_MAGIC.len() + 4 + 8 + 32;
+
+// Describes a value stored in chunks, stored against the key of the value itself.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct ChunkManifest {
+	pub(crate) chunk_count: u32,
+	pub(crate) total_length: u64,
+	pub(crate) hash: sha256::Hash,
+}
+
+impl ChunkManifest {
+	pub(crate) fn new(value: &[u8], chunk_count: u32) -> Self {
+		Self { chunk_count, total_length: value.len() as u64, hash: sha256::Hash::hash(value) }
+	}
+
+	pub(crate) fn encode(&self) -> Vec<u8> {
+		let mut manifest = Vec::with_capacity(MANIFEST_LENGTH);
+		manifest.extend_from_slice(MANIFEST_MAGIC);
+		manifest.extend_from_slice(&self.chunk_count.to_be_bytes());
+		manifest.extend_from_slice(&self.total_length.to_be_bytes());
+		manifest.extend_from_slice(self.hash.as_byte_array());
+		manifest
+	}
+
+	// Decodes the manifest from `value`, returning `None` if it is a plain value instead.
+	pub(crate) fn decode(value: &[u8], key: &str) -> Result<Option<Self>, VssError> {
+		if !is_manifest(value) {
+			return Ok(None);
+		}
+		if value.len() != MANIFEST_LENGTH {
+			return Err(VssError::ChunkedValueError(format!(
+				"Malformed chunk manifest for key: {}",
+				key
+			)));
+		}
+		let mut chunk_count = [0u8; 4];
+		chunk_count.copy_from_slice(&value[8..12]);
+		let mut total_length = [0u8; 8];
+		total_length.copy_from_slice(&value[12..20]);
+		// unwrap safety: the remaining 32 bytes are a valid hash.
+		let hash = sha256::Hash::from_slice(&value[20..]).unwrap();
+		Ok(Some(Self {
+			chunk_count: u32::from_be_bytes(chunk_count),
+			total_length: u64::from_be_bytes(total_length),
+			hash,
+		}))
+	}
+
+	// Verifies the reassembled `value` matches the manifest.
+	pub(crate) fn verify(&self, value: &[u8], key: &str) -> Result<(), VssError> {
+		if value.len() as u64 != self.total_length {
+			return Err(VssError::ChunkedValueError(format!(
+				"Expected {} bytes for key: {}, found: {}",
+				self.total_length,
+				key,
+				value.len()
+			)));
+		}
+		if sha256::Hash::hash(value) != self.hash {
+			return Err(VssError::ChunkedValueError(format!(
+				"Chunks of key: {} don't match their manifest, they may have been modified concurrently",
+				key
+			)));
+		}
+		Ok(())
+	}
+}
+
+// Returns whether `value` would be read as a manifest, i.e. whether it needs to be stored in
+// chunks, even if small enough to be stored as a plain value.
+pub(crate) fn is_manifest(value: &[u8]) -> bool {
+	value.starts_with(MANIFEST_MAGIC)
+}
+
+pub(crate) fn chunk_key(key: &str, index: u32) -> String {
+	format!("{}#chunk-{}", key, index)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn encodes_and_decodes_manifest() {
+		let value = vec![42u8; 10];
+		let manifest = ChunkManifest::new(&value, 3);
+		let encoded = manifest.encode();
+		assert_eq!(encoded.len(), MANIFEST_LENGTH);
+		assert_eq!(ChunkManifest::decode(&encoded, "k1").unwrap(), Some(manifest));
+
+		// Plain values aren't manifests, while truncated manifests are rejected.
+		assert_eq!(ChunkManifest::decode(&value, "k1").unwrap(), None);
+		let result = ChunkManifest::decode(&encoded[..MANIFEST_LENGTH - 1], "k1");
+		assert!(matches!(result, Err(VssError::ChunkedValueError(..))));
+	}
+
+	#[test]
+	fn verifies_reassembled_value() {
+		let value = vec![42u8; 10];
+		let manifest = ChunkManifest::new(&value, 3);
+		assert!(manifest.verify(&value, "k1").is_ok());
+		let result = manifest.verify(&value[..9], "k1");
+		assert!(matches!(result, Err(VssError::ChunkedValueError(..))));
+		let result = manifest.verify(&[43u8; 10], "k1");
+		assert!(matches!(result, Err(VssError::ChunkedValueError(..))));
+	}
+}
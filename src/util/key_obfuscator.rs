@@ -10,6 +10,9 @@ use crate::crypto::chacha20poly1305::ChaCha20Poly1305;
 /// keys to be used for VSS operations.
 ///
 /// It provides client-side deterministic encryption of given keys using ChaCha20-Poly1305.
+///
+/// The nonce is derived synthetically from the key (SIV-style), such that a given key always maps
+/// to the same obfuscated key, and is encoded as base64 without padding.
 pub struct KeyObfuscator {
 	obfuscation_key: [u8; 32],
 	hashing_key: [u8; 32],
@@ -33,7 +36,7 @@ impl KeyObfuscator {
 		let key_bytes = key.as_bytes();
 		let mut ciphertext =
 			Vec::with_capacity(key_bytes.len() + TAG_LENGTH + NONCE_LENGTH + TAG_LENGTH);
-		ciphertext.extend_from_slice(&key_bytes);
+		ciphertext.extend_from_slice(key_bytes);
 
 		// Encrypt key in-place using a synthetic nonce.
 		let (mut nonce, tag) = self.encrypt(&mut ciphertext, key.as_bytes());
@@ -51,6 +54,9 @@ impl KeyObfuscator {
 	}
 
 	/// Deobfuscates the given obfuscated_key.
+	///
+	/// Returns an error of kind [`ErrorKind::InvalidData`] if `obfuscated_key` was tampered with,
+	/// truncated or obfuscated under a different master key.
 	pub fn deobfuscate(&self, obfuscated_key: &str) -> Result<String, Error> {
 		let obfuscated_key_bytes = BASE64_STANDARD_NO_PAD.decode(obfuscated_key).map_err(|e| {
 			let msg = format!(
@@ -80,7 +86,7 @@ impl KeyObfuscator {
 
 		// Unwrap wrapped_nonce to get nonce.
 		let mut wrapped_nonce = [0u8; NONCE_LENGTH];
-		wrapped_nonce.clone_from_slice(&wrapped_nonce_bytes);
+		wrapped_nonce.clone_from_slice(wrapped_nonce_bytes);
 		self.decrypt(&mut wrapped_nonce, ciphertext, wrapped_nonce_tag).map_err(|_| {
 			let msg = format!(
 				"Failed to decrypt wrapped nonce, for key: {}, Invalid Tag.",
@@ -108,23 +114,21 @@ impl KeyObfuscator {
 	}
 
 	/// Encrypts the given plaintext in-place using a HMAC generated nonce.
-	fn encrypt(
-		&self, mut plaintext: &mut [u8], initial_nonce_material: &[u8],
-	) -> ([u8; 12], [u8; 16]) {
+	fn encrypt(&self, plaintext: &mut [u8], initial_nonce_material: &[u8]) -> ([u8; 12], [u8; 16]) {
 		let nonce = self.generate_synthetic_nonce(initial_nonce_material);
 		let mut cipher = ChaCha20Poly1305::new(&self.obfuscation_key, &nonce, &[]);
 		let mut tag = [0u8; TAG_LENGTH];
-		cipher.encrypt_inplace(&mut plaintext, &mut tag);
+		cipher.encrypt_inplace(plaintext, &mut tag);
 		(nonce, tag)
 	}
 
 	/// Decrypts the given ciphertext in-place using a HMAC generated nonce.
 	fn decrypt(
-		&self, mut ciphertext: &mut [u8], initial_nonce_material: &[u8], tag: &[u8],
+		&self, ciphertext: &mut [u8], initial_nonce_material: &[u8], tag: &[u8],
 	) -> Result<(), ()> {
 		let nonce = self.generate_synthetic_nonce(initial_nonce_material);
 		let mut cipher = ChaCha20Poly1305::new(&self.obfuscation_key, &nonce, &[]);
-		cipher.decrypt_inplace(&mut ciphertext, tag)
+		cipher.decrypt_inplace(ciphertext, tag)
 	}
 
 	/// Generate a HMAC based nonce using provided `initial_nonce_material`.
@@ -170,6 +174,36 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn deobfuscate_rejects_tampered_or_truncated_keys() {
+		let key_obfuscator = KeyObfuscator::new([42u8; 32]);
+		let obfuscated_key = key_obfuscator.obfuscate("a_semi_secret_key");
+
+		// Flipping any character of the ciphertext, tags or wrapped nonce fails authentication.
+		for i in 0..obfuscated_key.len() {
+			let mut tampered_key = obfuscated_key.clone().into_bytes();
+			tampered_key[i] = if tampered_key[i] == b'A' { b'B' } else { b'A' };
+			let tampered_key = String::from_utf8(tampered_key).unwrap();
+			assert!(key_obfuscator.deobfuscate(&tampered_key).is_err());
+		}
+
+		let truncated_key = &obfuscated_key[..obfuscated_key.len() - 4];
+		assert!(key_obfuscator.deobfuscate(truncated_key).is_err());
+		assert!(key_obfuscator.deobfuscate(&obfuscated_key[..8]).is_err());
+		assert!(key_obfuscator.deobfuscate("not*base64").is_err());
+	}
+
+	#[test]
+	fn obfuscation_depends_on_master_key() {
+		let key_obfuscator = KeyObfuscator::new([42u8; 32]);
+		let other_key_obfuscator = KeyObfuscator::new([43u8; 32]);
+		let obfuscated_key = key_obfuscator.obfuscate("a_semi_secret_key");
+		assert_ne!(obfuscated_key, other_key_obfuscator.obfuscate("a_semi_secret_key"));
+
+		// Keys obfuscated under a different master key can't be deobfuscated.
+		assert!(other_key_obfuscator.deobfuscate(&obfuscated_key).is_err());
+	}
+
 	use proptest::prelude::*;
 
 	proptest! {
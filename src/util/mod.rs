@@ -6,6 +6,15 @@ pub mod storable_builder;
 /// Contains retry utilities.
 pub mod retry;
 
+// Contains the client-side rate limiter of `VssClient`.
+pub(crate) mod rate_limiter;
+
+// Contains the format of values stored in chunks by `VssClient`.
+pub(crate) mod chunking;
+
+// Contains the client-side validation of requests sent by `VssClient`.
+pub(crate) mod validation;
+
 /// Contains [`KeyObfuscator`] utility.
 ///
 /// [`KeyObfuscator`]: key_obfuscator::KeyObfuscator
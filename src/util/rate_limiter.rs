@@ -0,0 +1,129 @@
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+
+use crate::error::VssError;
+use crate::resilience::RateLimitBehavior;
+
+// A token bucket, allowing bursts of up to `burst` requests, refilled at `requests_per_second`.
+//
+// With `RateLimitBehavior::Wait`, requests exceeding the available tokens reserve a future token,
+// such that concurrent requests are delayed in the order they arrived.
+pub(crate) struct RateLimiter {
+	requests_per_second: f64,
+	burst: f64,
+	behavior: RateLimitBehavior,
+	state: Mutex<BucketState>,
+}
+
+struct BucketState {
+	// May be negative, if future tokens were reserved.
+	tokens: f64,
+	last_refill: Instant,
+}
+
+impl RateLimiter {
+	pub(crate) fn new(requests_per_second: u32, burst: u32, behavior: RateLimitBehavior) -> Self {
+		let state = BucketState { tokens: burst as f64, last_refill: Instant::now() };
+		Self {
+			requests_per_second: requests_per_second as f64,
+			burst: burst as f64,
+			behavior,
+			state: Mutex::new(state),
+		}
+	}
+
+	// Waits until a request may be sent, or fails if it may not be sent right away, depending on
+	// the `RateLimitBehavior`.
+	pub(crate) async fn acquire(&self) -> Result<(), VssError> {
+		match self.behavior {
+			RateLimitBehavior::Wait => {
+				if let Some(delay) = self.reserve() {
+					tokio::time::sleep(delay).await;
+				}
+				Ok(())
+			},
+			RateLimitBehavior::Fail => {
+				self.try_take().map_err(|retry_after| VssError::RateLimitedError {
+					message: "Client-side rate limit exceeded".to_string(),
+					retry_after: Some(retry_after),
+					status: None,
+					request_id: None,
+				})
+			},
+		}
+	}
+
+	// Takes a token, returning the delay until it is available, if it isn't already.
+	fn reserve(&self) -> Option<Duration> {
+		let mut state = self.state.lock().unwrap();
+		self.refill(&mut state);
+		state.tokens -= 1.0;
+		if state.tokens >= 0.0 {
+			None
+		} else {
+			Some(Duration::from_secs_f64(-state.tokens / self.requests_per_second))
+		}
+	}
+
+	// Takes a token if available, or returns the delay until it is.
+	fn try_take(&self) -> Result<(), Duration> {
+		let mut state = self.state.lock().unwrap();
+		self.refill(&mut state);
+		if state.tokens >= 1.0 {
+			state.tokens -= 1.0;
+			Ok(())
+		} else {
+			Err(Duration::from_secs_f64((1.0 - state.tokens) / self.requests_per_second))
+		}
+	}
+
+	fn refill(&self, state: &mut BucketState) {
+		let now = Instant::now();
+		let refilled =
+			now.duration_since(state.last_refill).as_secs_f64() * self.requests_per_second;
+		state.tokens = (state.tokens + refilled).min(self.burst);
+		state.last_refill = now;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test(start_paused = true)]
+	async fn delays_requests_exceeding_burst() {
+		let rate_limiter = RateLimiter::new(10, 2, RateLimitBehavior::Wait);
+		assert_eq!(rate_limiter.reserve(), None);
+		assert_eq!(rate_limiter.reserve(), None);
+		// Further requests reserve future tokens, one per 100ms.
+		assert_eq!(rate_limiter.reserve(), Some(Duration::from_millis(100)));
+		assert_eq!(rate_limiter.reserve(), Some(Duration::from_millis(200)));
+
+		// Tokens are refilled over time, up to the burst size.
+		tokio::time::advance(Duration::from_secs(1)).await;
+		assert_eq!(rate_limiter.reserve(), None);
+		assert_eq!(rate_limiter.reserve(), None);
+		assert_eq!(rate_limiter.reserve(), Some(Duration::from_millis(100)));
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn fails_requests_exceeding_burst() {
+		let rate_limiter = RateLimiter::new(10, 2, RateLimitBehavior::Fail);
+		assert!(rate_limiter.acquire().await.is_ok());
+		assert!(rate_limiter.acquire().await.is_ok());
+		// Rejected requests don't take a token.
+		for _ in 0..2 {
+			match rate_limiter.acquire().await {
+				Err(VssError::RateLimitedError { retry_after, .. }) => {
+					assert_eq!(retry_after, Some(Duration::from_millis(100)));
+				},
+				result => panic!("Unexpected result: {:?}", result),
+			}
+		}
+
+		tokio::time::advance(Duration::from_millis(100)).await;
+		assert!(rate_limiter.acquire().await.is_ok());
+		assert!(rate_limiter.acquire().await.is_err());
+	}
+}
@@ -1,8 +1,13 @@
 use rand::Rng;
 use std::error::Error;
+use std::fmt;
 use std::future::Future;
 use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use tokio::time::Instant;
+
+use crate::error::VssError;
 
 /// A function that performs and retries the given operation according to a retry policy.
 ///
@@ -45,7 +50,7 @@ use std::time::Duration;
 /// impl SomeStruct {
 /// 	fn new() -> Self {
 /// 		let retry_policy = ExponentialBackoffRetryPolicy::new(Duration::from_millis(100))
-/// 			.skip_retry_on_error(Box::new(|e: &VssError| { matches!( e, VssError::NoSuchKeyError(..)) }) as _);
+/// 			.skip_retry_on_error(Box::new(|e: &VssError| { matches!( e, VssError::NoSuchKeyError { .. }) }) as _);
 /// 		Self { retry_policy }
 /// 	}
 /// }
@@ -57,30 +62,142 @@ where
 	Fut: Future<Output = Result<T, E>>,
 	E: Error,
 {
-	let mut attempts_made = 0;
-	let mut accumulated_delay = Duration::ZERO;
+	let mut state = RetryState::new();
 	loop {
+		retry_policy.before_attempt()?;
+		#[cfg(feature = "tracing")]
+		tracing::Span::current().record("retry.attempt", state.attempts_made + 1);
 		match operation().await {
-			Ok(result) => return Ok(result),
-			Err(err) => {
-				attempts_made += 1;
-				if let Some(delay) = retry_policy.next_delay(&RetryContext {
-					attempts_made,
-					accumulated_delay,
-					error: &err,
-				}) {
-					tokio::time::sleep(delay).await;
-					accumulated_delay += delay;
-				} else {
+			Ok(result) => {
+				retry_policy.on_success();
+				return Ok(result);
+			},
+			Err(err) => match state.next_delay(retry_policy, &err) {
+				Some(delay) => {
+					#[cfg(feature = "tracing")]
+					tracing::warn!(
+						operation = current_operation(),
+						attempt = state.attempts_made,
+						error = %err,
+						?delay,
+						"Retrying failed attempt"
+					);
+					#[cfg(feature = "metrics")]
+					crate::metrics::record_retry();
+					tokio::time::sleep(delay).await
+				},
+				None => {
+					#[cfg(feature = "tracing")]
+					if state.attempts_made > 1 {
+						tracing::error!(
+							operation = current_operation(),
+							attempts = state.attempts_made,
+							error = %err,
+							"Retry attempts exhausted"
+						);
+					}
 					return Err(err);
-				}
+				},
+			},
+		}
+	}
+}
+
+/// A blocking version of [`retry`], which performs and retries the given operation according to
+/// a retry policy, sleeping the current thread in between attempts.
+///
+/// **Caution**: A retry policy without the number of attempts capped by [`MaxAttemptsRetryPolicy`]
+/// decorator will result in infinite retries.
+pub fn retry_blocking<R, F, T, E>(mut operation: F, retry_policy: &R) -> Result<T, E>
+where
+	R: RetryPolicy<E = E>,
+	F: FnMut() -> Result<T, E>,
+	E: Error,
+{
+	let mut state = RetryState::new();
+	loop {
+		retry_policy.before_attempt()?;
+		#[cfg(feature = "tracing")]
+		tracing::Span::current().record("retry.attempt", state.attempts_made + 1);
+		match operation() {
+			Ok(result) => {
+				retry_policy.on_success();
+				return Ok(result);
 			},
+			Err(err) => match state.next_delay(retry_policy, &err) {
+				Some(delay) => {
+					#[cfg(feature = "tracing")]
+					tracing::warn!(
+						operation = current_operation(),
+						attempt = state.attempts_made,
+						error = %err,
+						?delay,
+						"Retrying failed attempt"
+					);
+					#[cfg(feature = "metrics")]
+					crate::metrics::record_retry();
+					std::thread::sleep(delay)
+				},
+				None => {
+					#[cfg(feature = "tracing")]
+					if state.attempts_made > 1 {
+						tracing::error!(
+							operation = current_operation(),
+							attempts = state.attempts_made,
+							error = %err,
+							"Retry attempts exhausted"
+						);
+					}
+					return Err(err);
+				},
+			},
+		}
+	}
+}
+
+// Returns the name of the operation being retried, as given by the current span, e.g.
+// `vss.get_object`. Neither keys nor values are recorded as part of retry events.
+#[cfg(feature = "tracing")]
+fn current_operation() -> &'static str {
+	tracing::Span::current().metadata().map_or("unknown", |metadata| metadata.name())
+}
+
+// The state of a retry operation, shared by `retry` and `retry_blocking`.
+struct RetryState {
+	attempts_made: u32,
+	accumulated_delay: Duration,
+	previous_delay: Duration,
+	start: Instant,
+}
+
+impl RetryState {
+	fn new() -> Self {
+		Self {
+			attempts_made: 0,
+			accumulated_delay: Duration::ZERO,
+			previous_delay: Duration::ZERO,
+			start: Instant::now(),
 		}
 	}
+
+	// Records a failed attempt and returns the delay before the next one, if any.
+	fn next_delay<R: RetryPolicy>(&mut self, retry_policy: &R, error: &R::E) -> Option<Duration> {
+		self.attempts_made += 1;
+		let delay = retry_policy.next_delay(&RetryContext {
+			attempts_made: self.attempts_made,
+			accumulated_delay: self.accumulated_delay,
+			elapsed: self.start.elapsed(),
+			previous_delay: self.previous_delay,
+			error,
+		})?;
+		self.accumulated_delay = self.accumulated_delay.saturating_add(delay);
+		self.previous_delay = delay;
+		Some(delay)
+	}
 }
 
 /// Provides the logic for how and when to perform retries.
-pub trait RetryPolicy: Sized {
+pub trait RetryPolicy {
 	/// The error type returned by the `operation` in `retry`.
 	type E: Error;
 
@@ -90,35 +207,195 @@ pub trait RetryPolicy: Sized {
 	/// If `None` is returned then no further retry attempt is made.
 	fn next_delay(&self, context: &RetryContext<Self::E>) -> Option<Duration>;
 
+	/// Called before each attempt, including the first one.
+	///
+	/// If an error is returned, the operation is not attempted and the error is returned
+	/// immediately without further retries. Defaults to always allowing the attempt.
+	fn before_attempt(&self) -> Result<(), Self::E> {
+		Ok(())
+	}
+
+	/// Called after an attempt succeeded. Does nothing by default.
+	fn on_success(&self) {}
+
 	/// Returns a new `RetryPolicy` that respects the given maximum attempts.
-	fn with_max_attempts(self, max_attempts: u32) -> MaxAttemptsRetryPolicy<Self> {
+	///
+	/// As the number of attempts made is tracked by [`retry`] and passed via [`RetryContext`], any
+	/// policy can be capped this way, including custom policies:
+	/// ```
+	/// # use std::time::Duration;
+	/// # use vss_client::error::VssError;
+	/// # use vss_client::util::retry::{RetryContext, RetryPolicy};
+	/// #
+	/// struct ConstantBackoff;
+	///
+	/// impl RetryPolicy for ConstantBackoff {
+	/// 	type E = VssError;
+	/// 	fn next_delay(&self, _context: &RetryContext<VssError>) -> Option<Duration> {
+	/// 		Some(Duration::from_millis(100))
+	/// 	}
+	/// }
+	///
+	/// let retry_policy = ConstantBackoff.with_max_attempts(5);
+	/// ```
+	fn with_max_attempts(self, max_attempts: u32) -> MaxAttemptsRetryPolicy<Self>
+	where
+		Self: Sized,
+	{
 		MaxAttemptsRetryPolicy { inner_policy: self, max_attempts }
 	}
 
+	/// Returns a new `RetryPolicy` that caps each delay at the given maximum delay.
+	///
+	/// As the cap applies to the delay returned by the underlying policy, decorating a
+	/// [`JitteredRetryPolicy`] ensures that the jittered delay never exceeds `max_delay`.
+	fn with_max_delay(self, max_delay: Duration) -> MaxDelayRetryPolicy<Self>
+	where
+		Self: Sized,
+	{
+		MaxDelayRetryPolicy { inner_policy: self, max_delay }
+	}
+
 	/// Returns a new `RetryPolicy` that respects the given total delay.
-	fn with_max_total_delay(self, max_total_delay: Duration) -> MaxTotalDelayRetryPolicy<Self> {
+	fn with_max_total_delay(self, max_total_delay: Duration) -> MaxTotalDelayRetryPolicy<Self>
+	where
+		Self: Sized,
+	{
 		MaxTotalDelayRetryPolicy { inner_policy: self, max_total_delay }
 	}
 
+	/// Returns a new `RetryPolicy` that respects the given total elapsed time.
+	///
+	/// Unlike [`RetryPolicy::with_max_total_delay`], the elapsed time also includes the time taken
+	/// to execute the operation, measured since the start of the first attempt. This can be used to
+	/// enforce a hard deadline on the whole retry operation.
+	fn with_max_elapsed_time(self, max_elapsed_time: Duration) -> MaxElapsedTimeRetryPolicy<Self>
+	where
+		Self: Sized,
+	{
+		MaxElapsedTimeRetryPolicy { inner_policy: self, max_elapsed_time }
+	}
+
 	/// Returns a new `RetryPolicy` that adds jitter(random delay) to underlying policy.
-	fn with_max_jitter(self, max_jitter: Duration) -> JitteredRetryPolicy<Self> {
-		JitteredRetryPolicy { inner_policy: self, max_jitter }
+	///
+	/// The jitter is drawn uniformly from `[0, max_jitter)` using [`RandomJitterSource`], a custom
+	/// source can be provided using [`JitteredRetryPolicy::with_jitter_source`].
+	fn with_max_jitter(self, max_jitter: Duration) -> JitteredRetryPolicy<Self>
+	where
+		Self: Sized,
+	{
+		JitteredRetryPolicy { inner_policy: self, max_jitter, jitter_source: RandomJitterSource }
+	}
+
+	/// Returns a new `RetryPolicy` that randomizes the delay of the underlying policy according to
+	/// the given [`JitterStrategy`].
+	///
+	/// The jitter is drawn using [`RandomJitterSource`], a custom source can be provided using
+	/// [`JitterStrategyRetryPolicy::with_jitter_source`].
+	fn with_jitter_strategy(self, strategy: JitterStrategy) -> JitterStrategyRetryPolicy<Self>
+	where
+		Self: Sized,
+	{
+		JitterStrategyRetryPolicy {
+			inner_policy: self,
+			strategy,
+			jitter_source: RandomJitterSource,
+		}
+	}
+
+	/// Returns a new `RetryPolicy` that honors the delay requested by the server via the
+	/// `Retry-After` header of a [`VssError::RateLimitedError`], instead of the delay computed by
+	/// the underlying policy.
+	///
+	/// The honored delay is capped at `max_retry_after`, so that a buggy or malicious server can't
+	/// stall the client for long. The underlying policy still decides whether to retry at all.
+	fn with_retry_after(self, max_retry_after: Duration) -> RetryAfterRetryPolicy<Self>
+	where
+		Self: RetryPolicy<E = VssError> + Sized,
+	{
+		RetryAfterRetryPolicy { inner_policy: self, max_retry_after }
+	}
+
+	/// Returns a new `RetryPolicy` that only retries errors with an HTTP status contained in
+	/// `statuses`, e.g. `&[429, 502, 503, 504]`, such that errors with any other status fail
+	/// immediately.
+	///
+	/// The status is given by [`VssError::http_status`] or, for errors such as a
+	/// [`VssError::ConflictError`] which always result from the same status, by
+	/// [`VssError::status_code`]. Errors without a response, e.g. transport errors such as a
+	/// refused connection or a timeout, are retried regardless of `statuses`. In either case, the
+	/// underlying policy still decides whether and when to retry.
+	fn with_retryable_statuses(self, statuses: &[u16]) -> RetryableStatusesRetryPolicy<Self>
+	where
+		Self: RetryPolicy<E = VssError> + Sized,
+	{
+		RetryableStatusesRetryPolicy { inner_policy: self, statuses: statuses.to_vec() }
+	}
+
+	/// Returns a new `RetryPolicy` that stops sending requests for `cool_down` after
+	/// `failure_threshold` consecutive server-side or network failures.
+	///
+	/// See [`CircuitBreakerRetryPolicy`] for details. It should be applied last, so that it observes
+	/// every error returned by the server.
+	fn with_circuit_breaker(
+		self, failure_threshold: u32, cool_down: Duration,
+	) -> CircuitBreakerRetryPolicy<Self>
+	where
+		Self: RetryPolicy<E = VssError> + Sized,
+	{
+		CircuitBreakerRetryPolicy::new(self, failure_threshold, cool_down)
 	}
 
 	/// Skips retrying on errors that evaluate to `true` after applying `function`.
+	///
+	/// The `function` is given the error of each failed attempt, which is returned as-is, without
+	/// any further attempts, if it evaluates to `true`.
 	fn skip_retry_on_error<F>(self, function: F) -> FilteredRetryPolicy<Self, F>
 	where
+		Self: Sized,
 		F: 'static + Fn(&Self::E) -> bool,
 	{
 		FilteredRetryPolicy { inner_policy: self, function }
 	}
 }
 
+/// A type-erased [`RetryPolicy`], which allows choosing the policy at runtime without making
+/// the containing types generic, see [`DynVssClient`].
+///
+/// [`DynVssClient`]: crate::client::DynVssClient
+pub type DynRetryPolicy = Arc<dyn RetryPolicy<E = VssError> + Send + Sync>;
+
+impl<T: RetryPolicy + ?Sized> RetryPolicy for Arc<T> {
+	type E = T::E;
+	fn next_delay(&self, context: &RetryContext<Self::E>) -> Option<Duration> {
+		(**self).next_delay(context)
+	}
+	fn before_attempt(&self) -> Result<(), Self::E> {
+		(**self).before_attempt()
+	}
+	fn on_success(&self) {
+		(**self).on_success()
+	}
+}
+
+impl<T: RetryPolicy + ?Sized> RetryPolicy for Box<T> {
+	type E = T::E;
+	fn next_delay(&self, context: &RetryContext<Self::E>) -> Option<Duration> {
+		(**self).next_delay(context)
+	}
+	fn before_attempt(&self) -> Result<(), Self::E> {
+		(**self).before_attempt()
+	}
+	fn on_success(&self) {
+		(**self).on_success()
+	}
+}
+
 /// Represents the context of a retry operation.
 ///
 /// The context holds key information about the retry operation
 /// such as how many attempts have been made until now, the accumulated
-/// delay between retries, and the error that triggered the retry.
+/// delay between retries, the elapsed time, and the error that triggered the retry.
 pub struct RetryContext<'a, E: Error> {
 	/// The number attempts made until now, before attempting the next retry.
 	attempts_made: u32,
@@ -127,39 +404,168 @@ pub struct RetryContext<'a, E: Error> {
 	/// attempts. Does not include the time taken to execute the operation.
 	accumulated_delay: Duration,
 
+	/// The time elapsed since the start of the first attempt. Includes both the time taken to
+	/// execute the operation and the delay in between previous attempts.
+	elapsed: Duration,
+
+	/// The delay waited before the previous attempt, zero if no retry was made yet.
+	previous_delay: Duration,
+
 	/// The error encountered in the previous attempt.
 	error: &'a E,
 }
 
+impl<'a, E: Error> RetryContext<'a, E> {
+	/// Returns the number of attempts made until now, before attempting the next retry.
+	pub fn attempts_made(&self) -> u32 {
+		self.attempts_made
+	}
+
+	/// Returns the amount of artificial delay already waited in between previous attempts.
+	pub fn accumulated_delay(&self) -> Duration {
+		self.accumulated_delay
+	}
+
+	/// Returns the time elapsed since the start of the first attempt.
+	pub fn elapsed(&self) -> Duration {
+		self.elapsed
+	}
+
+	/// Returns the delay waited before the previous attempt, zero if no retry was made yet.
+	pub fn previous_delay(&self) -> Duration {
+		self.previous_delay
+	}
+
+	/// Returns the error encountered in the previous attempt.
+	pub fn error(&self) -> &'a E {
+		self.error
+	}
+}
+
 /// The exponential backoff strategy is a retry approach that doubles the delay between retries.
 /// A combined exponential backoff and jitter strategy is recommended that is ["Exponential Backoff and Jitter"](https://aws.amazon.com/blogs/architecture/exponential-backoff-and-jitter/).
 /// This is helpful to avoid [Thundering Herd Problem](https://en.wikipedia.org/wiki/Thundering_herd_problem).
+///
+/// For [`VssError`]s, only errors which are [`VssError::is_transient`], i.e. server-side errors,
+/// network errors, timeouts and rate limiting, are retried. Errors such as conflicts, missing keys
+/// or rejected credentials are returned immediately, as they won't resolve on their own. Errors
+/// of any other type are always retried.
+///
+/// **Note**: Previous versions retried all errors, including [`VssError`]s which aren't transient.
+/// Retrying these requires a custom [`RetryPolicy`]. Classifying errors also requires the error
+/// type `E` to be `'static` for [`ExponentialBackoffRetryPolicy`] to implement [`RetryPolicy`].
 pub struct ExponentialBackoffRetryPolicy<E> {
 	/// The base delay duration for the backoff algorithm. First retry is `base_delay` after first attempt.
 	base_delay: Duration,
+	/// The factor by which the backoff grows with each attempt.
+	multiplier: f64,
 	phantom: PhantomData<E>,
 }
 
+impl<E> Clone for ExponentialBackoffRetryPolicy<E> {
+	fn clone(&self) -> Self {
+		Self { base_delay: self.base_delay, multiplier: self.multiplier, phantom: PhantomData }
+	}
+}
+
 impl<E: Error> ExponentialBackoffRetryPolicy<E> {
 	/// Constructs a new instance using `base_delay`.
 	///
 	/// `base_delay` is the base delay duration for the backoff algorithm. First retry is `base_delay`
 	/// after first attempt.
 	pub fn new(base_delay: Duration) -> ExponentialBackoffRetryPolicy<E> {
-		Self { base_delay, phantom: PhantomData }
+		Self { base_delay, multiplier: 2.0, phantom: PhantomData }
+	}
+
+	/// Sets the factor by which the backoff grows with each attempt, defaults to `2.0`.
+	///
+	/// After `n` attempts, the delay is `base_delay * (1 + multiplier + ... + multiplier^(n-1))`.
+	/// Values below `1.0` are treated as `1.0`, i.e. a linear backoff.
+	pub fn with_multiplier(mut self, multiplier: f64) -> ExponentialBackoffRetryPolicy<E> {
+		self.multiplier = multiplier.max(1.0);
+		self
 	}
 }
 
-impl<E: Error> RetryPolicy for ExponentialBackoffRetryPolicy<E> {
+impl<E: Error + 'static> RetryPolicy for ExponentialBackoffRetryPolicy<E> {
 	type E = E;
 	fn next_delay(&self, context: &RetryContext<Self::E>) -> Option<Duration> {
-		let backoff_factor = 2_u32.pow(context.attempts_made) - 1;
-		let delay = self.base_delay * backoff_factor;
-		Some(delay)
+		if !is_retryable(context.error) {
+			return None;
+		}
+		let backoff_factor = if self.multiplier == 1.0 {
+			context.attempts_made as f64
+		} else {
+			(self.multiplier.powi(context.attempts_made.min(i32::MAX as u32) as i32) - 1.0)
+				/ (self.multiplier - 1.0)
+		};
+		Some(scale_duration(self.base_delay, backoff_factor))
+	}
+}
+
+// Returns whether the given error may be retried, as classified by `VssError::is_transient`.
+// Errors of any other type are always retried.
+fn is_retryable<E: Error + 'static>(error: &E) -> bool {
+	let error: &(dyn Error + 'static) = error;
+	error.downcast_ref::<VssError>().map_or(true, VssError::is_transient)
+}
+
+// Multiplies `duration` by the non-negative `factor`, saturating at `Duration::MAX`.
+fn scale_duration(duration: Duration, factor: f64) -> Duration {
+	let secs = duration.as_secs_f64() * factor;
+	if !secs.is_finite() || secs >= Duration::MAX.as_secs_f64() {
+		Duration::MAX
+	} else if factor.fract() == 0.0 && factor <= u32::MAX as f64 {
+		// Avoid floating point imprecision for integral factors.
+		duration.checked_mul(factor as u32).unwrap_or(Duration::MAX)
+	} else {
+		Duration::from_secs_f64(secs)
+	}
+}
+
+/// A retry policy which never retries, i.e. the operation is attempted exactly once.
+///
+/// Useful when retries are handled elsewhere or aren't desired at all.
+pub struct NoRetryPolicy<E = VssError> {
+	phantom: PhantomData<E>,
+}
+
+impl<E: Error> NoRetryPolicy<E> {
+	/// Constructs a new instance.
+	pub fn new() -> Self {
+		Self { phantom: PhantomData }
+	}
+}
+
+impl<E: Error> Default for NoRetryPolicy<E> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<E> Clone for NoRetryPolicy<E> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<E> Copy for NoRetryPolicy<E> {}
+
+impl<E> fmt::Debug for NoRetryPolicy<E> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("NoRetryPolicy")
+	}
+}
+
+impl<E: Error> RetryPolicy for NoRetryPolicy<E> {
+	type E = E;
+	fn next_delay(&self, _context: &RetryContext<Self::E>) -> Option<Duration> {
+		None
 	}
 }
 
 /// Decorates the given `RetryPolicy` to respect the given maximum attempts.
+#[derive(Clone)]
 pub struct MaxAttemptsRetryPolicy<T: RetryPolicy> {
 	/// The underlying retry policy to use.
 	inner_policy: T,
@@ -169,8 +575,14 @@ pub struct MaxAttemptsRetryPolicy<T: RetryPolicy> {
 
 impl<T: RetryPolicy> RetryPolicy for MaxAttemptsRetryPolicy<T> {
 	type E = T::E;
+	fn before_attempt(&self) -> Result<(), Self::E> {
+		self.inner_policy.before_attempt()
+	}
+	fn on_success(&self) {
+		self.inner_policy.on_success()
+	}
 	fn next_delay(&self, context: &RetryContext<Self::E>) -> Option<Duration> {
-		if self.max_attempts == context.attempts_made {
+		if context.attempts_made >= self.max_attempts {
 			None
 		} else {
 			self.inner_policy.next_delay(context)
@@ -178,7 +590,30 @@ impl<T: RetryPolicy> RetryPolicy for MaxAttemptsRetryPolicy<T> {
 	}
 }
 
+/// Decorates the given `RetryPolicy` to cap each delay at the given maximum delay.
+#[derive(Clone)]
+pub struct MaxDelayRetryPolicy<T: RetryPolicy> {
+	/// The underlying retry policy to use.
+	inner_policy: T,
+	/// The maximum delay between two attempts.
+	max_delay: Duration,
+}
+
+impl<T: RetryPolicy> RetryPolicy for MaxDelayRetryPolicy<T> {
+	type E = T::E;
+	fn before_attempt(&self) -> Result<(), Self::E> {
+		self.inner_policy.before_attempt()
+	}
+	fn on_success(&self) {
+		self.inner_policy.on_success()
+	}
+	fn next_delay(&self, context: &RetryContext<Self::E>) -> Option<Duration> {
+		self.inner_policy.next_delay(context).map(|delay| delay.min(self.max_delay))
+	}
+}
+
 /// Decorates the given `RetryPolicy` to respect the given maximum total delay.
+#[derive(Clone)]
 pub struct MaxTotalDelayRetryPolicy<T: RetryPolicy> {
 	/// The underlying retry policy to use.
 	inner_policy: T,
@@ -188,10 +623,44 @@ pub struct MaxTotalDelayRetryPolicy<T: RetryPolicy> {
 
 impl<T: RetryPolicy> RetryPolicy for MaxTotalDelayRetryPolicy<T> {
 	type E = T::E;
+	fn before_attempt(&self) -> Result<(), Self::E> {
+		self.inner_policy.before_attempt()
+	}
+	fn on_success(&self) {
+		self.inner_policy.on_success()
+	}
+	fn next_delay(&self, context: &RetryContext<Self::E>) -> Option<Duration> {
+		let next_delay = self.inner_policy.next_delay(context);
+		if let Some(next_delay) = next_delay {
+			if self.max_total_delay < context.accumulated_delay.saturating_add(next_delay) {
+				return None;
+			}
+		}
+		next_delay
+	}
+}
+
+/// Decorates the given `RetryPolicy` to respect the given maximum total elapsed time.
+#[derive(Clone)]
+pub struct MaxElapsedTimeRetryPolicy<T: RetryPolicy> {
+	/// The underlying retry policy to use.
+	inner_policy: T,
+	/// The maximum time that will be allowed to elapse over all attempts, including delays.
+	max_elapsed_time: Duration,
+}
+
+impl<T: RetryPolicy> RetryPolicy for MaxElapsedTimeRetryPolicy<T> {
+	type E = T::E;
+	fn before_attempt(&self) -> Result<(), Self::E> {
+		self.inner_policy.before_attempt()
+	}
+	fn on_success(&self) {
+		self.inner_policy.on_success()
+	}
 	fn next_delay(&self, context: &RetryContext<Self::E>) -> Option<Duration> {
 		let next_delay = self.inner_policy.next_delay(context);
 		if let Some(next_delay) = next_delay {
-			if self.max_total_delay < context.accumulated_delay + next_delay {
+			if self.max_elapsed_time < context.elapsed.saturating_add(next_delay) {
 				return None;
 			}
 		}
@@ -201,28 +670,316 @@ impl<T: RetryPolicy> RetryPolicy for MaxTotalDelayRetryPolicy<T> {
 
 /// Decorates the given `RetryPolicy` and adds jitter (random delay) to it. This can make retries
 /// more spread out and less likely to all fail at once.
-pub struct JitteredRetryPolicy<T: RetryPolicy> {
+#[derive(Clone)]
+pub struct JitteredRetryPolicy<T: RetryPolicy, J: JitterSource = RandomJitterSource> {
 	/// The underlying retry policy to use.
 	inner_policy: T,
 	/// The maximum amount of random jitter to apply to the delay.
 	max_jitter: Duration,
+	/// The source used to draw the jitter from.
+	jitter_source: J,
+}
+
+impl<T: RetryPolicy, J: JitterSource> JitteredRetryPolicy<T, J> {
+	/// Uses the given `jitter_source` for drawing the jitter, e.g. to make retries deterministic
+	/// in tests.
+	pub fn with_jitter_source<J2: JitterSource>(
+		self, jitter_source: J2,
+	) -> JitteredRetryPolicy<T, J2> {
+		JitteredRetryPolicy {
+			inner_policy: self.inner_policy,
+			max_jitter: self.max_jitter,
+			jitter_source,
+		}
+	}
 }
 
-impl<T: RetryPolicy> RetryPolicy for JitteredRetryPolicy<T> {
+impl<T: RetryPolicy, J: JitterSource> RetryPolicy for JitteredRetryPolicy<T, J> {
 	type E = T::E;
+	fn before_attempt(&self) -> Result<(), Self::E> {
+		self.inner_policy.before_attempt()
+	}
+	fn on_success(&self) {
+		self.inner_policy.on_success()
+	}
 	fn next_delay(&self, context: &RetryContext<Self::E>) -> Option<Duration> {
 		if let Some(base_delay) = self.inner_policy.next_delay(context) {
-			let mut rng = rand::thread_rng();
-			let jitter =
-				Duration::from_micros(rng.gen_range(0..self.max_jitter.as_micros() as u64));
-			Some(base_delay + jitter)
+			let jitter = self.jitter_source.jitter(self.max_jitter);
+			Some(base_delay.saturating_add(jitter.min(self.max_jitter)))
 		} else {
 			None
 		}
 	}
 }
 
+/// The strategy used by [`JitterStrategyRetryPolicy`] to randomize retry delays.
+///
+/// See ["Exponential Backoff and Jitter"](https://aws.amazon.com/blogs/architecture/exponential-backoff-and-jitter/)
+/// for a comparison of the strategies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JitterStrategy {
+	/// The delay of the underlying policy is used as is.
+	None,
+	/// The delay is drawn uniformly from `[0, delay)`, where `delay` is the delay of the underlying
+	/// policy.
+	Full,
+	/// The delay is drawn uniformly from `[delay, max(delay, 3 * previous_delay))`, where `delay` is
+	/// the delay of the underlying policy and `previous_delay` is the delay waited before the
+	/// previous attempt.
+	///
+	/// Combine with [`RetryPolicy::with_max_delay`] to bound the resulting delay.
+	Decorrelated,
+}
+
+/// Decorates the given `RetryPolicy` and randomizes its delays according to a [`JitterStrategy`].
+#[derive(Clone)]
+pub struct JitterStrategyRetryPolicy<T: RetryPolicy, J: JitterSource = RandomJitterSource> {
+	/// The underlying retry policy to use.
+	inner_policy: T,
+	/// The strategy used to randomize the delay.
+	strategy: JitterStrategy,
+	/// The source used to draw the jitter from.
+	jitter_source: J,
+}
+
+impl<T: RetryPolicy, J: JitterSource> JitterStrategyRetryPolicy<T, J> {
+	/// Replaces the source used to draw the jitter from, e.g. to make delays deterministic in
+	/// tests.
+	pub fn with_jitter_source<J2: JitterSource>(
+		self, jitter_source: J2,
+	) -> JitterStrategyRetryPolicy<T, J2> {
+		JitterStrategyRetryPolicy {
+			inner_policy: self.inner_policy,
+			strategy: self.strategy,
+			jitter_source,
+		}
+	}
+}
+
+impl<T: RetryPolicy, J: JitterSource> RetryPolicy for JitterStrategyRetryPolicy<T, J> {
+	type E = T::E;
+	fn before_attempt(&self) -> Result<(), Self::E> {
+		self.inner_policy.before_attempt()
+	}
+	fn on_success(&self) {
+		self.inner_policy.on_success()
+	}
+	fn next_delay(&self, context: &RetryContext<Self::E>) -> Option<Duration> {
+		let delay = self.inner_policy.next_delay(context)?;
+		let delay = match self.strategy {
+			JitterStrategy::None => delay,
+			JitterStrategy::Full => self.jitter_source.jitter(delay).min(delay),
+			JitterStrategy::Decorrelated => {
+				let upper = delay.max(context.previous_delay.saturating_mul(3));
+				let range = upper - delay;
+				delay.saturating_add(self.jitter_source.jitter(range).min(range))
+			},
+		};
+		Some(delay)
+	}
+}
+
+/// Decorates the given `RetryPolicy` to honor the `Retry-After` delay requested by the server.
+///
+/// See [`RetryPolicy::with_retry_after`] for details.
+#[derive(Clone)]
+pub struct RetryAfterRetryPolicy<T: RetryPolicy<E = VssError>> {
+	/// The underlying retry policy to use.
+	inner_policy: T,
+	/// The maximum delay requested by the server that will be honored.
+	max_retry_after: Duration,
+}
+
+impl<T: RetryPolicy<E = VssError>> RetryPolicy for RetryAfterRetryPolicy<T> {
+	type E = VssError;
+	fn before_attempt(&self) -> Result<(), Self::E> {
+		self.inner_policy.before_attempt()
+	}
+	fn on_success(&self) {
+		self.inner_policy.on_success()
+	}
+	fn next_delay(&self, context: &RetryContext<Self::E>) -> Option<Duration> {
+		let next_delay = self.inner_policy.next_delay(context)?;
+		match context.error.retry_after() {
+			Some(retry_after) => Some(retry_after.min(self.max_retry_after)),
+			None => Some(next_delay),
+		}
+	}
+}
+
+/// Decorates the given `RetryPolicy` to only retry errors with one of the given HTTP statuses.
+///
+/// See [`RetryPolicy::with_retryable_statuses`] for details.
+#[derive(Clone)]
+pub struct RetryableStatusesRetryPolicy<T: RetryPolicy<E = VssError>> {
+	/// The underlying retry policy to use.
+	inner_policy: T,
+	/// The HTTP statuses of errors that may be retried.
+	statuses: Vec<u16>,
+}
+
+impl<T: RetryPolicy<E = VssError>> RetryPolicy for RetryableStatusesRetryPolicy<T> {
+	type E = VssError;
+	fn before_attempt(&self) -> Result<(), Self::E> {
+		self.inner_policy.before_attempt()
+	}
+	fn on_success(&self) {
+		self.inner_policy.on_success()
+	}
+	fn next_delay(&self, context: &RetryContext<Self::E>) -> Option<Duration> {
+		let status = context.error.http_status().or_else(|| context.error.status_code());
+		match status {
+			Some(status) if !self.statuses.contains(&status) => None,
+			_ => self.inner_policy.next_delay(context),
+		}
+	}
+}
+
+/// Decorates the given `RetryPolicy` with a circuit breaker, which fails fast while the VSS server
+/// is deemed to be unavailable.
+///
+/// After `failure_threshold` consecutive failures, i.e. [`VssError::InternalServerError`],
+/// [`VssError::TimeoutError`] or a [`VssError::InternalError`] due to a network error or a `5xx`
+/// response, the circuit opens and all attempts fail immediately with
+/// [`VssError::CircuitOpenError`] for the `cool_down` period. Afterwards, the circuit is half-open
+/// and a single probe attempt is allowed through: if it succeeds the circuit closes again,
+/// otherwise it re-opens for another `cool_down` period. Any other error is a response from a
+/// healthy server and resets the count of consecutive failures.
+///
+/// The state is shared between clones, so cloned [`VssClient`]s share a single circuit.
+///
+/// [`VssClient`]: crate::client::VssClient
+#[derive(Clone)]
+pub struct CircuitBreakerRetryPolicy<T: RetryPolicy<E = VssError>> {
+	/// The underlying retry policy to use.
+	inner_policy: T,
+	/// The number of consecutive failures after which the circuit opens.
+	failure_threshold: u32,
+	/// The duration for which the circuit stays open before a probe attempt is allowed.
+	cool_down: Duration,
+	/// The state of the circuit, shared between clones.
+	state: Arc<Mutex<CircuitState>>,
+}
+
+struct CircuitState {
+	consecutive_failures: u32,
+	status: CircuitStatus,
+}
+
+#[derive(Clone, Copy)]
+enum CircuitStatus {
+	Closed,
+	Open { since: Instant },
+	// A probe attempt was allowed at `since`, attempts are rejected until it completes.
+	HalfOpen { since: Instant },
+}
+
+impl<T: RetryPolicy<E = VssError>> CircuitBreakerRetryPolicy<T> {
+	/// Constructs a new instance decorating `inner_policy`, see
+	/// [`RetryPolicy::with_circuit_breaker`].
+	pub fn new(inner_policy: T, failure_threshold: u32, cool_down: Duration) -> Self {
+		let state = CircuitState { consecutive_failures: 0, status: CircuitStatus::Closed };
+		Self {
+			inner_policy,
+			failure_threshold: failure_threshold.max(1),
+			cool_down,
+			state: Arc::new(Mutex::new(state)),
+		}
+	}
+
+	/// Returns `true` if attempts are currently rejected, either because the circuit is open or a
+	/// probe attempt is in progress.
+	pub fn is_open(&self) -> bool {
+		let state = self.state.lock().unwrap();
+		match state.status {
+			CircuitStatus::Closed => false,
+			CircuitStatus::Open { since } | CircuitStatus::HalfOpen { since } => {
+				since.elapsed() < self.cool_down
+			},
+		}
+	}
+}
+
+impl<T: RetryPolicy<E = VssError>> RetryPolicy for CircuitBreakerRetryPolicy<T> {
+	type E = VssError;
+	fn before_attempt(&self) -> Result<(), Self::E> {
+		let mut state = self.state.lock().unwrap();
+		match state.status {
+			CircuitStatus::Closed => {},
+			// A probe which didn't complete within the cool-down, e.g. because it was cancelled, is
+			// superseded by a new one.
+			CircuitStatus::Open { since } | CircuitStatus::HalfOpen { since } => {
+				if since.elapsed() < self.cool_down {
+					return Err(VssError::CircuitOpenError(format!(
+						"Circuit opened after {} consecutive failures",
+						state.consecutive_failures
+					)));
+				}
+				state.status = CircuitStatus::HalfOpen { since: Instant::now() };
+			},
+		}
+		drop(state);
+		self.inner_policy.before_attempt()
+	}
+
+	fn on_success(&self) {
+		let mut state = self.state.lock().unwrap();
+		state.consecutive_failures = 0;
+		state.status = CircuitStatus::Closed;
+		drop(state);
+		self.inner_policy.on_success()
+	}
+
+	fn next_delay(&self, context: &RetryContext<Self::E>) -> Option<Duration> {
+		let mut state = self.state.lock().unwrap();
+		match context.error {
+			error if error.is_server_failure() => {
+				state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+				match state.status {
+					CircuitStatus::Closed
+						if state.consecutive_failures < self.failure_threshold => {},
+					CircuitStatus::Closed | CircuitStatus::HalfOpen { .. } => {
+						state.status = CircuitStatus::Open { since: Instant::now() };
+						return None;
+					},
+					CircuitStatus::Open { .. } => return None,
+				}
+			},
+			_ => {
+				state.consecutive_failures = 0;
+				state.status = CircuitStatus::Closed;
+			},
+		}
+		drop(state);
+		self.inner_policy.next_delay(context)
+	}
+}
+
+/// A source of randomness used by [`JitteredRetryPolicy`] and [`JitterStrategyRetryPolicy`] for
+/// computing jitter.
+pub trait JitterSource {
+	/// Returns a random duration in the range `[0, max_jitter)`, or zero if `max_jitter` is zero.
+	fn jitter(&self, max_jitter: Duration) -> Duration;
+}
+
+/// A [`JitterSource`] drawing jitter uniformly at random using the thread-local random number
+/// generator.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RandomJitterSource;
+
+impl JitterSource for RandomJitterSource {
+	fn jitter(&self, max_jitter: Duration) -> Duration {
+		let max_jitter_micros = max_jitter.as_micros() as u64;
+		if max_jitter_micros == 0 {
+			return Duration::ZERO;
+		}
+		let mut rng = rand::thread_rng();
+		Duration::from_micros(rng.gen_range(0..max_jitter_micros))
+	}
+}
+
 /// Decorates the given `RetryPolicy` by not retrying on errors that match the given function.
+#[derive(Clone)]
 pub struct FilteredRetryPolicy<T: RetryPolicy, F> {
 	inner_policy: T,
 	function: F,
@@ -235,11 +992,412 @@ where
 	E: Error,
 {
 	type E = T::E;
+	fn before_attempt(&self) -> Result<(), Self::E> {
+		self.inner_policy.before_attempt()
+	}
+	fn on_success(&self) {
+		self.inner_policy.on_success()
+	}
 	fn next_delay(&self, context: &RetryContext<E>) -> Option<Duration> {
-		if (self.function)(&context.error) {
+		if (self.function)(context.error) {
 			None
 		} else {
 			self.inner_policy.next_delay(context)
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::rngs::StdRng;
+	use rand::SeedableRng;
+	use std::io;
+	use std::sync::Mutex;
+
+	struct FixedJitterSource(Duration);
+
+	impl JitterSource for FixedJitterSource {
+		fn jitter(&self, _max_jitter: Duration) -> Duration {
+			self.0
+		}
+	}
+
+	fn context(attempts_made: u32, error: &io::Error) -> RetryContext<'_, io::Error> {
+		RetryContext {
+			attempts_made,
+			accumulated_delay: Duration::ZERO,
+			elapsed: Duration::ZERO,
+			previous_delay: Duration::ZERO,
+			error,
+		}
+	}
+
+	struct SeededJitterSource(Mutex<StdRng>);
+
+	impl SeededJitterSource {
+		fn new(seed: u64) -> Self {
+			Self(Mutex::new(StdRng::seed_from_u64(seed)))
+		}
+	}
+
+	impl JitterSource for SeededJitterSource {
+		fn jitter(&self, max_jitter: Duration) -> Duration {
+			let max_jitter_micros = max_jitter.as_micros() as u64;
+			if max_jitter_micros == 0 {
+				return Duration::ZERO;
+			}
+			Duration::from_micros(self.0.lock().unwrap().gen_range(0..max_jitter_micros))
+		}
+	}
+
+	#[test]
+	fn jitter_strategies_stay_within_bounds() {
+		let error = io::Error::new(io::ErrorKind::Other, "test");
+		let base_delay = Duration::from_millis(100);
+
+		let retry_policy = ExponentialBackoffRetryPolicy::new(base_delay)
+			.with_jitter_strategy(JitterStrategy::None);
+		assert_eq!(retry_policy.next_delay(&context(2, &error)), Some(Duration::from_millis(300)));
+
+		let retry_policy = ExponentialBackoffRetryPolicy::new(base_delay)
+			.with_jitter_strategy(JitterStrategy::Full)
+			.with_jitter_source(SeededJitterSource::new(42));
+		for attempts_made in 1..=5 {
+			let computed_delay = base_delay * (2_u32.pow(attempts_made) - 1);
+			for _ in 0..100 {
+				let delay = retry_policy.next_delay(&context(attempts_made, &error)).unwrap();
+				assert!(delay < computed_delay);
+			}
+		}
+
+		let retry_policy = ExponentialBackoffRetryPolicy::new(base_delay)
+			.with_jitter_strategy(JitterStrategy::Decorrelated)
+			.with_jitter_source(SeededJitterSource::new(42));
+		let mut previous_delay = Duration::ZERO;
+		for attempts_made in 1..=5 {
+			let computed_delay = base_delay * (2_u32.pow(attempts_made) - 1);
+			let mut context = context(attempts_made, &error);
+			context.previous_delay = previous_delay;
+			let delay = retry_policy.next_delay(&context).unwrap();
+			assert!(delay >= computed_delay);
+			assert!(delay <= computed_delay.max(previous_delay * 3));
+			previous_delay = delay;
+		}
+	}
+
+	#[test]
+	fn retry_after_is_honored_and_capped() {
+		let retry_policy = ExponentialBackoffRetryPolicy::new(Duration::from_millis(100))
+			.with_max_attempts(3)
+			.with_retry_after(Duration::from_secs(10));
+		let rate_limited = |retry_after| VssError::RateLimitedError {
+			message: "Too many requests".to_string(),
+			retry_after,
+			status: None,
+			request_id: None,
+		};
+
+		let error = rate_limited(Some(Duration::from_secs(2)));
+		assert_eq!(retry_policy.next_delay(&vss_context(1, &error)), Some(Duration::from_secs(2)));
+		let error = rate_limited(Some(Duration::from_secs(3600)));
+		assert_eq!(retry_policy.next_delay(&vss_context(1, &error)), Some(Duration::from_secs(10)));
+		// Without a requested delay, the delay of the underlying policy is used.
+		let error = rate_limited(None);
+		assert_eq!(
+			retry_policy.next_delay(&vss_context(1, &error)),
+			Some(Duration::from_millis(100))
+		);
+		// The underlying policy still decides whether to retry.
+		let error = rate_limited(Some(Duration::from_secs(2)));
+		assert_eq!(retry_policy.next_delay(&vss_context(3, &error)), None);
+	}
+
+	#[test]
+	fn only_retryable_statuses_are_retried() {
+		let retry_policy = ExponentialBackoffRetryPolicy::new(Duration::from_millis(100))
+			.with_max_attempts(3)
+			.with_retryable_statuses(&[429, 502, 503, 504]);
+		let server_error = |status| VssError::InternalServerError {
+			message: "Failure".to_string(),
+			status: Some(status),
+			request_id: None,
+		};
+
+		for status in [502, 503, 504] {
+			let error = server_error(status);
+			assert!(retry_policy.next_delay(&vss_context(1, &error)).is_some(), "{}", status);
+		}
+		for status in [500, 520] {
+			let error = server_error(status);
+			assert_eq!(retry_policy.next_delay(&vss_context(1, &error)), None, "{}", status);
+		}
+		let error = VssError::RateLimitedError {
+			message: "Too many requests".to_string(),
+			retry_after: None,
+			status: Some(429),
+			request_id: None,
+		};
+		assert!(retry_policy.next_delay(&vss_context(1, &error)).is_some());
+
+		// Errors without a status, e.g. transport errors, are retried regardless.
+		let error = VssError::TimeoutError {
+			message: "Timed out".to_string(),
+			source: None,
+			request_id: None,
+		};
+		assert!(retry_policy.next_delay(&vss_context(1, &error)).is_some());
+		// The underlying policy still decides whether to retry.
+		let error = server_error(502);
+		assert_eq!(retry_policy.next_delay(&vss_context(3, &error)), None);
+	}
+
+	#[test]
+	fn retryable_statuses_apply_to_errors_with_fixed_statuses() {
+		struct AlwaysRetry;
+
+		impl RetryPolicy for AlwaysRetry {
+			type E = VssError;
+			fn next_delay(&self, _context: &RetryContext<VssError>) -> Option<Duration> {
+				Some(Duration::from_millis(100))
+			}
+		}
+
+		let retry_policy = AlwaysRetry.with_retryable_statuses(&[502, 503]);
+		let conflict_error = VssError::ConflictError {
+			message: "Conflict".to_string(),
+			key: None,
+			expected_version: None,
+			current_version: None,
+			request_id: None,
+		};
+		let no_such_key_error = VssError::NoSuchKeyError {
+			message: "Missing".to_string(),
+			store_id: None,
+			key: None,
+			request_id: None,
+		};
+		for error in [
+			conflict_error,
+			no_such_key_error,
+			VssError::AuthError("Unauthorized".to_string()),
+			VssError::InvalidRequestError("Invalid".to_string()),
+			VssError::InternalServerError {
+				message: "Failure".to_string(),
+				status: None,
+				request_id: None,
+			},
+		] {
+			assert_eq!(retry_policy.next_delay(&vss_context(1, &error)), None, "{}", error);
+		}
+
+		let error = VssError::TimeoutError {
+			message: "Timed out".to_string(),
+			source: None,
+			request_id: None,
+		};
+		assert!(retry_policy.next_delay(&vss_context(1, &error)).is_some());
+	}
+
+	#[test]
+	fn exponential_backoff_skips_permanent_errors() {
+		let retry_policy =
+			ExponentialBackoffRetryPolicy::new(Duration::from_millis(100)).with_max_attempts(3);
+		let conflict_error = VssError::ConflictError {
+			message: "Conflict".to_string(),
+			key: None,
+			expected_version: None,
+			current_version: None,
+			request_id: None,
+		};
+		let no_such_key_error = VssError::NoSuchKeyError {
+			message: "Missing".to_string(),
+			store_id: None,
+			key: None,
+			request_id: None,
+		};
+		for error in [
+			conflict_error,
+			no_such_key_error,
+			VssError::AuthError("Unauthorized".to_string()),
+			VssError::InvalidRequestError("Invalid".to_string()),
+		] {
+			assert_eq!(retry_policy.next_delay(&vss_context(1, &error)), None, "{}", error);
+		}
+
+		let error = VssError::InternalServerError {
+			message: "Failure".to_string(),
+			status: None,
+			request_id: None,
+		};
+		assert!(retry_policy.next_delay(&vss_context(1, &error)).is_some());
+	}
+
+	#[test]
+	fn no_retry_policy_never_retries() {
+		let error = io::Error::new(io::ErrorKind::Other, "test");
+		let retry_policy = NoRetryPolicy::<io::Error>::default();
+		assert_eq!(retry_policy.next_delay(&context(1, &error)), None);
+	}
+
+	fn vss_context(attempts_made: u32, error: &VssError) -> RetryContext<'_, VssError> {
+		RetryContext {
+			attempts_made,
+			accumulated_delay: Duration::ZERO,
+			elapsed: Duration::ZERO,
+			previous_delay: Duration::ZERO,
+			error,
+		}
+	}
+
+	#[test]
+	fn circuit_breaker_transitions() {
+		let cool_down = Duration::from_millis(50);
+		let retry_policy = ExponentialBackoffRetryPolicy::new(Duration::from_millis(10))
+			.with_max_attempts(5)
+			.with_circuit_breaker(2, cool_down);
+		let server_error = VssError::InternalServerError {
+			message: "Failure".to_string(),
+			status: None,
+			request_id: None,
+		};
+		let conflict_error = VssError::ConflictError {
+			message: "Conflict".to_string(),
+			key: None,
+			expected_version: None,
+			current_version: None,
+			request_id: None,
+		};
+
+		// Failures below the threshold are retried as usual, other errors reset the count.
+		assert!(retry_policy.before_attempt().is_ok());
+		assert!(retry_policy.next_delay(&vss_context(1, &server_error)).is_some());
+		assert!(retry_policy.next_delay(&vss_context(2, &conflict_error)).is_none());
+		assert!(retry_policy.next_delay(&vss_context(3, &server_error)).is_some());
+		assert!(!retry_policy.is_open());
+
+		// Reaching the threshold opens the circuit, which fails fast for clones as well.
+		assert!(retry_policy.next_delay(&vss_context(4, &server_error)).is_none());
+		let cloned_policy = retry_policy.clone();
+		assert!(cloned_policy.is_open());
+		assert!(matches!(cloned_policy.before_attempt(), Err(VssError::CircuitOpenError(..))));
+
+		// After the cool-down a single probe is allowed, whose failure re-opens the circuit.
+		std::thread::sleep(cool_down);
+		assert!(retry_policy.before_attempt().is_ok());
+		assert!(matches!(cloned_policy.before_attempt(), Err(VssError::CircuitOpenError(..))));
+		assert!(retry_policy.next_delay(&vss_context(1, &server_error)).is_none());
+		assert!(matches!(retry_policy.before_attempt(), Err(VssError::CircuitOpenError(..))));
+
+		// A successful probe closes the circuit again.
+		std::thread::sleep(cool_down);
+		assert!(retry_policy.before_attempt().is_ok());
+		retry_policy.on_success();
+		assert!(!cloned_policy.is_open());
+		assert!(cloned_policy.before_attempt().is_ok());
+		assert!(cloned_policy.next_delay(&vss_context(1, &server_error)).is_some());
+	}
+
+	#[test]
+	fn exponential_backoff_grows_with_attempts() {
+		let error = io::Error::new(io::ErrorKind::Other, "test");
+		let retry_policy = ExponentialBackoffRetryPolicy::new(Duration::from_millis(100));
+		let delays: Vec<Duration> =
+			(1..=4).map(|n| retry_policy.next_delay(&context(n, &error)).unwrap()).collect();
+		assert_eq!(
+			delays,
+			vec![
+				Duration::from_millis(100),
+				Duration::from_millis(300),
+				Duration::from_millis(700),
+				Duration::from_millis(1500)
+			]
+		);
+
+		let retry_policy =
+			ExponentialBackoffRetryPolicy::new(Duration::from_millis(100)).with_multiplier(3.0);
+		let delays: Vec<Duration> =
+			(1..=3).map(|n| retry_policy.next_delay(&context(n, &error)).unwrap()).collect();
+		assert_eq!(
+			delays,
+			vec![
+				Duration::from_millis(100),
+				Duration::from_millis(400),
+				Duration::from_millis(1300)
+			]
+		);
+
+		let retry_policy =
+			ExponentialBackoffRetryPolicy::new(Duration::from_millis(100)).with_multiplier(1.0);
+		assert_eq!(retry_policy.next_delay(&context(3, &error)), Some(Duration::from_millis(300)));
+
+		// Large attempt counts saturate instead of overflowing.
+		let retry_policy = ExponentialBackoffRetryPolicy::new(Duration::from_millis(100));
+		assert_eq!(retry_policy.next_delay(&context(5000, &error)), Some(Duration::MAX));
+
+		// Adding jitter to a saturated delay doesn't overflow either.
+		let retry_policy = ExponentialBackoffRetryPolicy::new(Duration::from_millis(100))
+			.with_max_jitter(Duration::from_millis(50))
+			.with_jitter_source(FixedJitterSource(Duration::from_millis(40)));
+		assert_eq!(retry_policy.next_delay(&context(5000, &error)), Some(Duration::MAX));
+	}
+
+	#[test]
+	fn max_delay_caps_each_delay() {
+		let error = io::Error::new(io::ErrorKind::Other, "test");
+		let retry_policy = ExponentialBackoffRetryPolicy::new(Duration::from_millis(100))
+			.with_max_jitter(Duration::from_millis(50))
+			.with_jitter_source(FixedJitterSource(Duration::from_millis(40)))
+			.with_max_delay(Duration::from_millis(500));
+		assert_eq!(retry_policy.next_delay(&context(1, &error)), Some(Duration::from_millis(140)));
+		assert_eq!(retry_policy.next_delay(&context(2, &error)), Some(Duration::from_millis(340)));
+		assert_eq!(retry_policy.next_delay(&context(3, &error)), Some(Duration::from_millis(500)));
+		assert_eq!(retry_policy.next_delay(&context(10, &error)), Some(Duration::from_millis(500)));
+	}
+
+	#[test]
+	fn max_attempts_stops_retrying() {
+		let error = io::Error::new(io::ErrorKind::Other, "test");
+		let retry_policy =
+			ExponentialBackoffRetryPolicy::new(Duration::from_millis(100)).with_max_attempts(3);
+		assert!(retry_policy.next_delay(&context(1, &error)).is_some());
+		assert!(retry_policy.next_delay(&context(2, &error)).is_some());
+		assert!(retry_policy.next_delay(&context(3, &error)).is_none());
+	}
+
+	#[test]
+	fn jitter_is_added_to_delay() {
+		let error = io::Error::new(io::ErrorKind::Other, "test");
+		let retry_policy = ExponentialBackoffRetryPolicy::new(Duration::from_millis(100))
+			.with_max_jitter(Duration::from_millis(10))
+			.with_jitter_source(FixedJitterSource(Duration::from_millis(7)));
+
+		assert_eq!(retry_policy.next_delay(&context(1, &error)), Some(Duration::from_millis(107)));
+		assert_eq!(retry_policy.next_delay(&context(2, &error)), Some(Duration::from_millis(307)));
+	}
+
+	#[test]
+	fn jitter_is_bounded_by_max_jitter() {
+		let error = io::Error::new(io::ErrorKind::Other, "test");
+		let retry_policy = ExponentialBackoffRetryPolicy::new(Duration::from_millis(100))
+			.with_max_jitter(Duration::from_millis(10))
+			.with_jitter_source(FixedJitterSource(Duration::from_secs(1)));
+		assert_eq!(retry_policy.next_delay(&context(1, &error)), Some(Duration::from_millis(110)));
+
+		let retry_policy = ExponentialBackoffRetryPolicy::new(Duration::from_millis(100))
+			.with_max_jitter(Duration::from_millis(10));
+		for _ in 0..100 {
+			let delay = retry_policy.next_delay(&context(1, &error)).unwrap();
+			assert!(delay >= Duration::from_millis(100));
+			assert!(delay < Duration::from_millis(110));
+		}
+	}
+
+	#[test]
+	fn zero_max_jitter_adds_no_jitter() {
+		let error = io::Error::new(io::ErrorKind::Other, "test");
+		let retry_policy = ExponentialBackoffRetryPolicy::new(Duration::from_millis(100))
+			.with_max_jitter(Duration::ZERO);
+		assert_eq!(retry_policy.next_delay(&context(1, &error)), Some(Duration::from_millis(100)));
+	}
+}
@@ -1,21 +1,100 @@
+#[cfg(feature = "gzip")]
+use crate::compression::{self, CompressionLevel};
 use crate::crypto::chacha20poly1305::ChaCha20Poly1305;
-use crate::types::{EncryptionMetadata, PlaintextBlob, Storable};
+use crate::envelope::{EncryptionMetadata, PlaintextBlob, Storable};
 use ::prost::Message;
 use std::borrow::Borrow;
+use std::collections::HashMap;
 use std::io;
 use std::io::{Error, ErrorKind};
 
 /// [`StorableBuilder`] is a utility to build and deconstruct [`Storable`] objects.
 /// It provides client-side Encrypt-then-MAC using ChaCha20-Poly1305.
+///
+/// The `store_id` and `key` a [`Storable`] is stored under are bound to it as associated data, such
+/// that it can't be deconstructed if it was moved to a different key, e.g. by swapping the values
+/// of two keys server-side.
+///
+/// Supports key rotation by identifying the data encryption key used for each [`Storable`], see
+/// [`Self::with_keys`].
+///
+/// With the `gzip` feature, values may be compressed before being encrypted, see
+/// `StorableBuilder::compress_values`.
 pub struct StorableBuilder<T: EntropySource> {
-	data_encryption_key: [u8; 32],
+	data_encryption_keys: HashMap<String, [u8; 32]>,
+	current_key_id: String,
 	entropy_source: T,
+	accept_legacy_storables: bool,
+	#[cfg(feature = "gzip")]
+	compression: Option<(CompressionLevel, usize)>,
 }
 
 impl<T: EntropySource> StorableBuilder<T> {
 	/// Constructs a new instance.
+	///
+	/// The `data_encryption_key` is identified by the empty key id, such that it can be given as
+	/// such to [`Self::with_keys`] once rotated.
 	pub fn new(data_encryption_key: [u8; 32], entropy_source: T) -> StorableBuilder<T> {
-		Self { data_encryption_key, entropy_source }
+		let data_encryption_keys = HashMap::from([(String::new(), data_encryption_key)]);
+		Self {
+			data_encryption_keys,
+			current_key_id: String::new(),
+			entropy_source,
+			accept_legacy_storables: true,
+			#[cfg(feature = "gzip")]
+			compression: None,
+		}
+	}
+
+	/// Constructs a new instance using the given data encryption keys by their key id.
+	///
+	/// New [`Storable`]s are built using the key identified by `current_key_id`, while all keys can
+	/// be used to deconstruct existing ones, which may then be migrated via [`Self::reencrypt`].
+	/// [`Storable`]s built by a [`StorableBuilder`] constructed via [`Self::new`] are identified
+	/// by the empty key id.
+	///
+	/// Returns an error of kind [`ErrorKind::InvalidInput`] if `data_encryption_keys` doesn't
+	/// contain `current_key_id`.
+	pub fn with_keys(
+		data_encryption_keys: HashMap<String, [u8; 32]>, current_key_id: String, entropy_source: T,
+	) -> io::Result<StorableBuilder<T>> {
+		if !data_encryption_keys.contains_key(&current_key_id) {
+			let msg = format!("Missing the current data encryption key: {}", current_key_id);
+			return Err(Error::new(ErrorKind::InvalidInput, msg));
+		}
+		Ok(Self {
+			data_encryption_keys,
+			current_key_id,
+			entropy_source,
+			accept_legacy_storables: true,
+			#[cfg(feature = "gzip")]
+			compression: None,
+		})
+	}
+
+	/// Sets whether legacy [`Storable`]s, which were built without being bound to their `store_id`
+	/// and `key`, are accepted by [`Self::deconstruct`]. Defaults to `true`.
+	///
+	/// This allows reading values written by previous versions, which should be re-written to
+	/// migrate them, as legacy [`Storable`]s aren't protected against being moved to a different
+	/// key. Once all values were migrated, this should be set to `false`, such that a legacy
+	/// [`Storable`] can't be substituted for one bound to its key.
+	pub fn accept_legacy_storables(mut self, accept_legacy_storables: bool) -> Self {
+		self.accept_legacy_storables = accept_legacy_storables;
+		self
+	}
+
+	/// Sets [`Self::build`] to gzip-compress values of at least `min_size` bytes before encrypting
+	/// them. Disabled by default.
+	///
+	/// Values are only stored compressed if that actually reduces their size, such that tiny or
+	/// incompressible values aren't inflated. The compression is recorded alongside the encrypted
+	/// value, such that [`Self::deconstruct`] transparently decompresses it, independent of this
+	/// setting.
+	#[cfg(feature = "gzip")]
+	pub fn compress_values(mut self, level: CompressionLevel, min_size: usize) -> Self {
+		self.compression = Some((level, min_size));
+		self
 	}
 }
 
@@ -29,31 +108,42 @@ pub trait EntropySource {
 	fn fill_bytes(&self, buffer: &mut [u8]);
 }
 
-const CHACHA20_CIPHER_NAME: &'static str = "ChaCha20Poly1305";
+// The cipher format of legacy `Storable`s, built without associated data.
+const CHACHA20_CIPHER_NAME: &str = "ChaCha20Poly1305";
+// The cipher format of `Storable`s bound to their `store_id` and `key` as associated data.
+const CHACHA20_AAD_CIPHER_NAME: &str = "ChaCha20Poly1305+AAD";
+const NONCE_LENGTH: usize = 12;
+const TAG_LENGTH: usize = 16;
+#[cfg(feature = "gzip")]
+const GZIP_COMPRESSION: &str = "gzip";
 
 impl<T: EntropySource> StorableBuilder<T> {
 	/// Creates a [`Storable`] that can be serialized and stored as `value` in [`PutObjectRequest`].
 	///
-	/// Uses ChaCha20 for encrypting `input` and Poly1305 for generating a mac/tag.
+	/// Uses ChaCha20 for encrypting `input` and Poly1305 for generating a mac/tag, authenticating
+	/// the `store_id` and `key` the [`Storable`] is stored under as associated data.
 	///
 	/// Refer to docs on [`Storable`] for more information.
 	///
 	/// [`PutObjectRequest`]: crate::types::PutObjectRequest
-	pub fn build(&self, input: Vec<u8>, version: i64) -> Storable {
-		let mut nonce = vec![0u8; 12];
+	pub fn build(&self, input: Vec<u8>, version: i64, store_id: &str, key: &str) -> Storable {
+		let mut nonce = vec![0u8; NONCE_LENGTH];
 		self.entropy_source.fill_bytes(&mut nonce[4..]);
 
-		let mut data_blob = PlaintextBlob { value: input, version }.encode_to_vec();
+		let mut data_blob = self.compressed_blob(input, version).encode_to_vec();
 
-		let mut cipher = ChaCha20Poly1305::new(&self.data_encryption_key, &nonce, &[]);
-		let mut tag = vec![0u8; 16];
+		let aad = associated_data(store_id, key);
+		let data_encryption_key = &self.data_encryption_keys[&self.current_key_id];
+		let mut cipher = ChaCha20Poly1305::new(data_encryption_key, &nonce, &aad);
+		let mut tag = vec![0u8; TAG_LENGTH];
 		cipher.encrypt_inplace(&mut data_blob, &mut tag);
 		Storable {
 			data: data_blob,
 			encryption_metadata: Some(EncryptionMetadata {
 				nonce,
 				tag,
-				cipher_format: CHACHA20_CIPHER_NAME.to_string(),
+				cipher_format: CHACHA20_AAD_CIPHER_NAME.to_string(),
+				key_id: self.current_key_id.clone(),
 			}),
 		}
 	}
@@ -61,11 +151,49 @@ impl<T: EntropySource> StorableBuilder<T> {
 	/// Deconstructs the provided [`Storable`] and returns constituent decrypted data and its
 	/// corresponding version as stored at the time of [`PutObjectRequest`].
 	///
+	/// The `store_id` and `key` need to be the ones given to [`Self::build`].
+	///
+	/// Returns an error of kind [`ErrorKind::InvalidData`] if the [`Storable`] is malformed, was
+	/// tampered with, was encrypted using a different key or was built for a different `store_id`
+	/// or `key`. Legacy [`Storable`]s are rejected if [`Self::accept_legacy_storables`] is unset.
+	/// Returns an error of kind [`ErrorKind::NotFound`], naming the key id, if the [`Storable`]
+	/// was built using a data encryption key which isn't known.
+	///
 	/// [`PutObjectRequest`]: crate::types::PutObjectRequest
-	pub fn deconstruct(&self, mut storable: Storable) -> io::Result<(Vec<u8>, i64)> {
-		let encryption_metadata = storable.encryption_metadata.unwrap();
+	pub fn deconstruct(
+		&self, mut storable: Storable, store_id: &str, key: &str,
+	) -> io::Result<(Vec<u8>, i64)> {
+		let encryption_metadata = storable
+			.encryption_metadata
+			.ok_or_else(|| Error::new(ErrorKind::InvalidData, "Missing encryption metadata"))?;
+		let aad = match encryption_metadata.cipher_format.as_str() {
+			CHACHA20_AAD_CIPHER_NAME => associated_data(store_id, key),
+			CHACHA20_CIPHER_NAME if self.accept_legacy_storables => Vec::new(),
+			CHACHA20_CIPHER_NAME => {
+				let msg = "Legacy Storable without associated data is not accepted";
+				return Err(Error::new(ErrorKind::InvalidData, msg));
+			},
+			cipher_format => {
+				let msg = format!("Unknown cipher format: {}", cipher_format);
+				return Err(Error::new(ErrorKind::InvalidData, msg));
+			},
+		};
+		// Nonces are generated with 4 leading zero bytes, as required by the cipher.
+		let nonce = &encryption_metadata.nonce;
+		if nonce.len() != NONCE_LENGTH || nonce[..4] != [0u8; 4] {
+			return Err(Error::new(ErrorKind::InvalidData, "Invalid nonce"));
+		}
+		if encryption_metadata.tag.len() != TAG_LENGTH {
+			return Err(Error::new(ErrorKind::InvalidData, "Invalid Tag"));
+		}
+		let data_encryption_key =
+			self.data_encryption_keys.get(&encryption_metadata.key_id).ok_or_else(|| {
+				let msg =
+					format!("Unknown data encryption key id: {:?}", encryption_metadata.key_id);
+				Error::new(ErrorKind::NotFound, msg)
+			})?;
 		let mut cipher =
-			ChaCha20Poly1305::new(&self.data_encryption_key, &encryption_metadata.nonce, &[]);
+			ChaCha20Poly1305::new(data_encryption_key, &encryption_metadata.nonce, &aad);
 
 		cipher
 			.decrypt_inplace(&mut storable.data, encryption_metadata.tag.borrow())
@@ -73,13 +201,65 @@ impl<T: EntropySource> StorableBuilder<T> {
 
 		let data_blob = PlaintextBlob::decode(&storable.data[..])
 			.map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
-		Ok((data_blob.value, data_blob.version))
+		let value = decompress(data_blob.value, &data_blob.compression)?;
+		Ok((value, data_blob.version))
 	}
+
+	/// Re-encrypts the given [`Storable`] using the current data encryption key, retaining its
+	/// data and version.
+	///
+	/// Fails like [`Self::deconstruct`] if the [`Storable`] can't be deconstructed.
+	pub fn reencrypt(&self, storable: Storable, store_id: &str, key: &str) -> io::Result<Storable> {
+		let (data, version) = self.deconstruct(storable, store_id, key)?;
+		Ok(self.build(data, version, store_id, key))
+	}
+
+	// The compression is recorded in the encrypted `PlaintextBlob` rather than in the
+	// `EncryptionMetadata`, as the latter isn't authenticated.
+	fn compressed_blob(&self, value: Vec<u8>, version: i64) -> PlaintextBlob {
+		#[cfg(feature = "gzip")]
+		if let Some((level, min_size)) = self.compression {
+			if value.len() >= min_size {
+				if let Ok(compressed) = compression::gzip(&value, level) {
+					if compressed.len() < value.len() {
+						let compression = GZIP_COMPRESSION.to_string();
+						return PlaintextBlob { value: compressed, version, compression };
+					}
+				}
+			}
+		}
+		PlaintextBlob { value, version, compression: String::new() }
+	}
+}
+
+fn decompress(value: Vec<u8>, compression: &str) -> io::Result<Vec<u8>> {
+	match compression {
+		"" => Ok(value),
+		#[cfg(feature = "gzip")]
+		GZIP_COMPRESSION => {
+			compression::gunzip(&value).map_err(|e| Error::new(ErrorKind::InvalidData, e))
+		},
+		_ => {
+			let msg = format!("Unsupported compression: {}", compression);
+			Err(Error::new(ErrorKind::InvalidData, msg))
+		},
+	}
+}
+
+// Returns the associated data binding a `Storable` to the `store_id` and `key` it's stored under.
+// The `store_id` is length-prefixed, such that different pairs never result in the same data.
+fn associated_data(store_id: &str, key: &str) -> Vec<u8> {
+	let mut aad = Vec::with_capacity(8 + store_id.len() + key.len());
+	aad.extend_from_slice(&(store_id.len() as u64).to_be_bytes());
+	aad.extend_from_slice(store_id.as_bytes());
+	aad.extend_from_slice(key.as_bytes());
+	aad
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::types;
 
 	pub struct TestEntropyProvider;
 	impl EntropySource for TestEntropyProvider {
@@ -97,16 +277,171 @@ mod tests {
 		let test_entropy_provider = TestEntropyProvider;
 		let mut data_key = [0u8; 32];
 		test_entropy_provider.fill_bytes(&mut data_key);
-		let storable_builder = StorableBuilder {
-			data_encryption_key: data_key,
-			entropy_source: test_entropy_provider,
-		};
+		let storable_builder = StorableBuilder::new(data_key, test_entropy_provider);
 		let expected_data = b"secret".to_vec();
 		let expected_version = 8;
-		let storable = storable_builder.build(expected_data.clone(), expected_version);
+		let storable =
+			storable_builder.build(expected_data.clone(), expected_version, "store", "k1");
 
-		let (actual_data, actual_version) = storable_builder.deconstruct(storable).unwrap();
+		let (actual_data, actual_version) =
+			storable_builder.deconstruct(storable, "store", "k1").unwrap();
 		assert_eq!(actual_data, expected_data);
 		assert_eq!(actual_version, expected_version);
 	}
+
+	#[test]
+	fn deconstruct_fails_for_invalid_storables() {
+		let storable_builder = StorableBuilder::new([42u8; 32], TestEntropyProvider);
+		let storable = storable_builder.build(b"secret".to_vec(), 8, "store", "k1");
+		let deconstruct = |storable: Storable| {
+			let error = storable_builder.deconstruct(storable, "store", "k1").unwrap_err();
+			assert_eq!(error.kind(), ErrorKind::InvalidData);
+		};
+
+		// Encrypted using a different key.
+		let other_storable_builder = StorableBuilder::new([43u8; 32], TestEntropyProvider);
+		assert!(other_storable_builder.deconstruct(storable.clone(), "store", "k1").is_err());
+
+		let mut bit_flipped = storable.clone();
+		bit_flipped.data[0] ^= 1;
+		deconstruct(bit_flipped);
+
+		let mut truncated_data = storable.clone();
+		truncated_data.data.pop();
+		deconstruct(truncated_data);
+
+		let mut truncated_nonce = storable.clone();
+		truncated_nonce.encryption_metadata.as_mut().unwrap().nonce.pop();
+		deconstruct(truncated_nonce);
+
+		let mut invalid_nonce = storable.clone();
+		invalid_nonce.encryption_metadata.as_mut().unwrap().nonce[0] = 1;
+		deconstruct(invalid_nonce);
+
+		let mut truncated_tag = storable.clone();
+		truncated_tag.encryption_metadata.as_mut().unwrap().tag.pop();
+		deconstruct(truncated_tag);
+
+		let mut unknown_cipher = storable.clone();
+		unknown_cipher.encryption_metadata.as_mut().unwrap().cipher_format = "AES".to_string();
+		deconstruct(unknown_cipher);
+
+		// Downgrading to a legacy Storable doesn't circumvent the associated data.
+		let mut downgraded = storable.clone();
+		downgraded.encryption_metadata.as_mut().unwrap().cipher_format =
+			CHACHA20_CIPHER_NAME.to_string();
+		deconstruct(downgraded);
+
+		deconstruct(Storable { data: storable.data, encryption_metadata: None });
+	}
+
+	#[test]
+	fn deconstruct_fails_for_different_store_id_or_key() {
+		let storable_builder = StorableBuilder::new([42u8; 32], TestEntropyProvider);
+		let storable = storable_builder.build(b"secret".to_vec(), 8, "store", "k1");
+
+		assert!(storable_builder.deconstruct(storable.clone(), "store", "k2").is_err());
+		assert!(storable_builder.deconstruct(storable.clone(), "other_store", "k1").is_err());
+		// The store_id and key can't be shifted into one another.
+		assert!(storable_builder.deconstruct(storable.clone(), "storek", "1").is_err());
+		assert!(storable_builder.deconstruct(storable, "store", "k1").is_ok());
+	}
+
+	#[test]
+	fn deconstructs_legacy_storables_unless_rejected() {
+		// A Storable built without associated data by previous versions.
+		let data_encryption_key = [42u8; 32];
+		let mut data =
+			types::PlaintextBlob { value: b"secret".to_vec(), version: 8 }.encode_to_vec();
+		let nonce = vec![0u8; NONCE_LENGTH];
+		let mut tag = vec![0u8; TAG_LENGTH];
+		ChaCha20Poly1305::new(&data_encryption_key, &nonce, &[])
+			.encrypt_inplace(&mut data, &mut tag);
+		// As encoded using the messages of `vss.proto`, which lack the `key_id` and `compression`.
+		let encoded_storable = types::Storable {
+			data,
+			encryption_metadata: Some(types::EncryptionMetadata {
+				nonce,
+				tag,
+				cipher_format: CHACHA20_CIPHER_NAME.to_string(),
+			}),
+		}
+		.encode_to_vec();
+		let legacy_storable = Storable::decode(&encoded_storable[..]).unwrap();
+		assert_eq!(legacy_storable.encryption_metadata.as_ref().unwrap().key_id, "");
+
+		let storable_builder = StorableBuilder::new(data_encryption_key, TestEntropyProvider);
+		let (data, version) =
+			storable_builder.deconstruct(legacy_storable.clone(), "store", "k1").unwrap();
+		assert_eq!(data, b"secret".to_vec());
+		assert_eq!(version, 8);
+
+		let storable_builder = storable_builder.accept_legacy_storables(false);
+		assert!(storable_builder.deconstruct(legacy_storable, "store", "k1").is_err());
+	}
+
+	#[test]
+	fn rotates_data_encryption_keys() {
+		let old_storable_builder = StorableBuilder::new([42u8; 32], TestEntropyProvider);
+		let old_storable = old_storable_builder.build(b"secret".to_vec(), 8, "store", "k1");
+
+		let keys = HashMap::from([(String::new(), [42u8; 32]), ("v2".to_string(), [43u8; 32])]);
+		let storable_builder =
+			StorableBuilder::with_keys(keys, "v2".to_string(), TestEntropyProvider).unwrap();
+		let (data, version) =
+			storable_builder.deconstruct(old_storable.clone(), "store", "k1").unwrap();
+		assert_eq!((data, version), (b"secret".to_vec(), 8));
+
+		// Re-encrypted storables use the current key, retaining their data and version.
+		let storable = storable_builder.reencrypt(old_storable, "store", "k1").unwrap();
+		assert_eq!(storable.encryption_metadata.as_ref().unwrap().key_id, "v2");
+		let (data, version) =
+			storable_builder.deconstruct(storable.clone(), "store", "k1").unwrap();
+		assert_eq!((data, version), (b"secret".to_vec(), 8));
+
+		// Storables of unknown keys are rejected, naming the missing key.
+		let error = old_storable_builder.deconstruct(storable, "store", "k1").unwrap_err();
+		assert_eq!(error.kind(), ErrorKind::NotFound);
+		assert!(error.to_string().contains("\"v2\""));
+
+		let keys = HashMap::from([("v1".to_string(), [42u8; 32])]);
+		let result = StorableBuilder::with_keys(keys, "v2".to_string(), TestEntropyProvider);
+		assert_eq!(result.err().unwrap().kind(), ErrorKind::InvalidInput);
+	}
+
+	#[cfg(feature = "gzip")]
+	#[test]
+	fn compresses_values_above_threshold() {
+		use rand::RngCore;
+
+		let storable_builder = StorableBuilder::new([42u8; 32], TestEntropyProvider)
+			.compress_values(CompressionLevel::default(), 64);
+		let round_trip = |data: &[u8]| {
+			let storable = storable_builder.build(data.to_vec(), 8, "store", "k1");
+			let stored_len = storable.data.len();
+			let (actual_data, version) =
+				storable_builder.deconstruct(storable, "store", "k1").unwrap();
+			assert_eq!((actual_data.as_slice(), version), (data, 8));
+			stored_len
+		};
+
+		let compressible = vec![7u8; 4096];
+		assert!(round_trip(&compressible) < 128);
+
+		// Incompressible and small values are stored uncompressed.
+		let mut incompressible = vec![0u8; 4096];
+		rand::thread_rng().fill_bytes(&mut incompressible);
+		assert!(round_trip(&incompressible) > incompressible.len());
+		assert!(round_trip(&[7u8; 63]) > 63);
+
+		// Uncompressed storables, e.g. of previous versions, are still deconstructed, and
+		// compressed ones regardless of the compression setting.
+		let plain_storable_builder = StorableBuilder::new([42u8; 32], TestEntropyProvider);
+		let storable = plain_storable_builder.build(compressible.clone(), 8, "store", "k1");
+		let (data, _) = storable_builder.deconstruct(storable, "store", "k1").unwrap();
+		assert_eq!(data, compressible);
+		let storable = storable_builder.build(compressible.clone(), 8, "store", "k1");
+		let (data, _) = plain_storable_builder.deconstruct(storable, "store", "k1").unwrap();
+		assert_eq!(data, compressible);
+	}
 }
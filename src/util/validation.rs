@@ -0,0 +1,175 @@
+use crate::error::VssError;
+use crate::types::{DeleteObjectRequest, GetObjectRequest, KeyValue, PutObjectRequest};
+
+// The version of non-conditional writes and deletes, being the only valid negative version.
+const NON_CONDITIONAL_VERSION: i64 = -1;
+
+// Checks the given requests for violations of the API contract, before they're sent to the server.
+
+pub(crate) fn validate_get_request(request: &GetObjectRequest) -> Result<(), VssError> {
+	validate_store_id(&request.store_id)?;
+	validate_key(&request.key)
+}
+
+pub(crate) fn validate_put_request(
+	request: &PutObjectRequest, max_value_size: Option<usize>,
+) -> Result<(), VssError> {
+	validate_store_id(&request.store_id)?;
+	if request.transaction_items.is_empty() && request.delete_items.is_empty() {
+		return Err(VssError::InvalidRequestError(
+			"PutObjectRequest contains neither transaction_items nor delete_items".to_string(),
+		));
+	}
+	for item in request.transaction_items.iter() {
+		validate_key_value(item)?;
+		match max_value_size {
+			Some(max_value_size) if item.value.len() > max_value_size => {
+				return Err(VssError::InvalidRequestError(format!(
+					"Value of key: {} exceeds the maximum size of {} bytes, found: {}",
+					item.key,
+					max_value_size,
+					item.value.len()
+				)));
+			},
+			_ => {},
+		}
+	}
+	request.delete_items.iter().try_for_each(validate_key_value)
+}
+
+pub(crate) fn validate_delete_request(request: &DeleteObjectRequest) -> Result<(), VssError> {
+	validate_store_id(&request.store_id)?;
+	match &request.key_value {
+		Some(key_value) => validate_key_value(key_value),
+		None => Err(VssError::InvalidRequestError(
+			"key_value missing in DeleteObjectRequest".to_string(),
+		)),
+	}
+}
+
+fn validate_store_id(store_id: &str) -> Result<(), VssError> {
+	if store_id.is_empty() {
+		return Err(VssError::InvalidRequestError("store_id must not be empty".to_string()));
+	}
+	Ok(())
+}
+
+fn validate_key(key: &str) -> Result<(), VssError> {
+	if key.is_empty() {
+		return Err(VssError::InvalidRequestError("key must not be empty".to_string()));
+	}
+	Ok(())
+}
+
+fn validate_key_value(key_value: &KeyValue) -> Result<(), VssError> {
+	validate_key(&key_value.key)?;
+	if key_value.version < NON_CONDITIONAL_VERSION {
+		return Err(VssError::InvalidRequestError(format!(
+			"Invalid version for key: {}, must be at least -1, found: {}",
+			key_value.key, key_value.version
+		)));
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn key_value(key: &str, version: i64, value: &[u8]) -> KeyValue {
+		KeyValue { key: key.to_string(), version, value: value.to_vec() }
+	}
+
+	fn put_request(
+		store_id: &str, transaction_items: Vec<KeyValue>, delete_items: Vec<KeyValue>,
+	) -> PutObjectRequest {
+		PutObjectRequest {
+			store_id: store_id.to_string(),
+			global_version: None,
+			transaction_items,
+			delete_items,
+		}
+	}
+
+	fn assert_invalid(result: Result<(), VssError>) {
+		assert!(matches!(result, Err(VssError::InvalidRequestError(..))), "{:?}", result);
+	}
+
+	#[test]
+	fn accepts_valid_requests() {
+		let get_request = GetObjectRequest { store_id: "store".to_string(), key: "k1".to_string() };
+		assert!(validate_get_request(&get_request).is_ok());
+
+		let request = put_request(
+			"store",
+			vec![key_value("k1", 0, b"k1v1"), key_value("k2", -1, b"k2v1")],
+			vec![key_value("k3", 2, b"")],
+		);
+		assert!(validate_put_request(&request, Some(4)).is_ok());
+		assert!(validate_put_request(&request, None).is_ok());
+		let request = put_request("store", vec![], vec![key_value("k3", -1, b"")]);
+		assert!(validate_put_request(&request, Some(4)).is_ok());
+
+		let delete_request = DeleteObjectRequest {
+			store_id: "store".to_string(),
+			key_value: Some(key_value("k1", 3, b"")),
+		};
+		assert!(validate_delete_request(&delete_request).is_ok());
+	}
+
+	#[test]
+	fn rejects_empty_store_id() {
+		let get_request = GetObjectRequest { store_id: String::new(), key: "k1".to_string() };
+		assert_invalid(validate_get_request(&get_request));
+		let request = put_request("", vec![key_value("k1", 0, b"k1v1")], vec![]);
+		assert_invalid(validate_put_request(&request, None));
+		let delete_request = DeleteObjectRequest {
+			store_id: String::new(),
+			key_value: Some(key_value("k1", 1, b"")),
+		};
+		assert_invalid(validate_delete_request(&delete_request));
+	}
+
+	#[test]
+	fn rejects_empty_key() {
+		let get_request = GetObjectRequest { store_id: "store".to_string(), key: String::new() };
+		assert_invalid(validate_get_request(&get_request));
+		let request = put_request("store", vec![key_value("", 0, b"v1")], vec![]);
+		assert_invalid(validate_put_request(&request, None));
+		let request =
+			put_request("store", vec![key_value("k1", 0, b"k1v1")], vec![key_value("", 1, b"")]);
+		assert_invalid(validate_put_request(&request, None));
+		let delete_request = DeleteObjectRequest {
+			store_id: "store".to_string(),
+			key_value: Some(key_value("", 1, b"")),
+		};
+		assert_invalid(validate_delete_request(&delete_request));
+	}
+
+	#[test]
+	fn rejects_invalid_version() {
+		let request = put_request("store", vec![key_value("k1", -2, b"k1v1")], vec![]);
+		assert_invalid(validate_put_request(&request, None));
+		let request = put_request("store", vec![], vec![key_value("k1", -2, b"")]);
+		assert_invalid(validate_put_request(&request, None));
+		let delete_request = DeleteObjectRequest {
+			store_id: "store".to_string(),
+			key_value: Some(key_value("k1", -2, b"")),
+		};
+		assert_invalid(validate_delete_request(&delete_request));
+	}
+
+	#[test]
+	fn rejects_values_exceeding_max_size() {
+		let request = put_request("store", vec![key_value("k1", 0, b"k1v1+")], vec![]);
+		assert_invalid(validate_put_request(&request, Some(4)));
+		assert!(validate_put_request(&request, Some(5)).is_ok());
+	}
+
+	#[test]
+	fn rejects_requests_without_items() {
+		assert_invalid(validate_put_request(&put_request("store", vec![], vec![]), None));
+		let delete_request = DeleteObjectRequest { store_id: "store".to_string(), key_value: None };
+		assert_invalid(validate_delete_request(&delete_request));
+	}
+}
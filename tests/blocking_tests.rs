@@ -0,0 +1,221 @@
+#[cfg(feature = "blocking")]
+mod blocking_tests {
+	use mockito::{self, Matcher};
+	use prost::Message;
+	use reqwest::header::CONTENT_TYPE;
+	use std::collections::HashMap;
+	use std::time::Duration;
+	use vss_client::blocking::{VssBlockingClient, VssClientBlockingWrapper};
+	use vss_client::client::VssClientBuilder;
+	use vss_client::error::VssError;
+	use vss_client::types::{
+		DeleteObjectRequest, DeleteObjectResponse, ErrorCode, ErrorResponse, GetObjectRequest,
+		GetObjectResponse, KeyValue, ListKeyVersionsRequest, ListKeyVersionsResponse,
+		PutObjectRequest, PutObjectResponse,
+	};
+	use vss_client::util::retry::{ExponentialBackoffRetryPolicy, RetryPolicy};
+
+	const APPLICATION_OCTET_STREAM: &str = "application/octet-stream";
+
+	const GET_OBJECT_ENDPOINT: &str = "/getObject";
+	const PUT_OBJECT_ENDPOINT: &str = "/putObjects";
+	const DELETE_OBJECT_ENDPOINT: &str = "/deleteObject";
+	const LIST_KEY_VERSIONS_ENDPOINT: &str = "/listKeyVersions";
+
+	#[test]
+	fn test_blocking_get() {
+		let base_url = mockito::server_url().to_string();
+
+		let get_request = GetObjectRequest { store_id: "store".to_string(), key: "k1".to_string() };
+		let mock_response = GetObjectResponse {
+			value: Some(KeyValue { key: "k1".to_string(), version: 2, value: b"k1v2".to_vec() }),
+		};
+
+		// Register the mock endpoint with the mockito server and provide expected headers.
+		let mock_server = mockito::mock("POST", GET_OBJECT_ENDPOINT)
+			.match_header(CONTENT_TYPE.as_str(), APPLICATION_OCTET_STREAM)
+			.match_header("headerkey", "headervalue")
+			.match_body(get_request.encode_to_vec())
+			.with_status(200)
+			.with_body(mock_response.encode_to_vec())
+			.create();
+
+		let headers = HashMap::from([("headerkey".to_string(), "headervalue".to_string())]);
+		let client = VssBlockingClient::new_with_headers(base_url, retry_policy(), headers);
+		let actual_result = client.get_object(&get_request).unwrap();
+
+		assert_eq!(actual_result, mock_response);
+		mock_server.expect(1).assert();
+	}
+
+	#[test]
+	fn test_blocking_put_delete_list() {
+		let base_url = mockito::server_url().to_string();
+		let client = VssBlockingClient::new(base_url, retry_policy());
+
+		let put_request = PutObjectRequest {
+			store_id: "store".to_string(),
+			global_version: Some(4),
+			transaction_items: vec![KeyValue {
+				key: "k1".to_string(),
+				version: 2,
+				value: b"k1v3".to_vec(),
+			}],
+			delete_items: vec![],
+		};
+		let put_mock_server = mockito::mock("POST", PUT_OBJECT_ENDPOINT)
+			.match_body(put_request.encode_to_vec())
+			.with_status(200)
+			.with_body(PutObjectResponse {}.encode_to_vec())
+			.create();
+		assert_eq!(client.put_object(&put_request).unwrap(), PutObjectResponse {});
+
+		let delete_request = DeleteObjectRequest {
+			store_id: "store".to_string(),
+			key_value: Some(KeyValue { key: "k1".to_string(), version: 3, value: vec![] }),
+		};
+		let delete_mock_server = mockito::mock("POST", DELETE_OBJECT_ENDPOINT)
+			.match_body(delete_request.encode_to_vec())
+			.with_status(200)
+			.with_body(DeleteObjectResponse {}.encode_to_vec())
+			.create();
+		assert_eq!(client.delete_object(&delete_request).unwrap(), DeleteObjectResponse {});
+
+		let list_request = ListKeyVersionsRequest {
+			store_id: "store".to_string(),
+			page_size: Some(5),
+			page_token: None,
+			key_prefix: Some("k".into()),
+		};
+		let list_response = ListKeyVersionsResponse {
+			key_versions: vec![KeyValue { key: "k1".to_string(), version: 3, value: vec![] }],
+			next_page_token: Some("k1".into()),
+			global_version: Some(5),
+		};
+		let list_mock_server = mockito::mock("POST", LIST_KEY_VERSIONS_ENDPOINT)
+			.match_body(list_request.encode_to_vec())
+			.with_status(200)
+			.with_body(list_response.encode_to_vec())
+			.create();
+		assert_eq!(client.list_key_versions(&list_request).unwrap(), list_response);
+
+		put_mock_server.expect(1).assert();
+		delete_mock_server.expect(1).assert();
+		list_mock_server.expect(1).assert();
+	}
+
+	#[test]
+	fn test_blocking_retries_and_error_handling() {
+		let base_url = mockito::server_url().to_string();
+		let client = VssBlockingClient::new(base_url, retry_policy());
+
+		let error_response = ErrorResponse {
+			error_code: ErrorCode::InternalServerException.into(),
+			message: "InternalServerException".to_string(),
+		};
+		let mock_server = mockito::mock("POST", Matcher::Any)
+			.with_status(500)
+			.with_body(error_response.encode_to_vec())
+			.create();
+
+		let get_request = GetObjectRequest { store_id: "store".to_string(), key: "k1".to_string() };
+		let get_result = client.get_object(&get_request);
+		assert!(matches!(get_result.unwrap_err(), VssError::InternalServerError { .. }));
+
+		// Verify 3 requests hit the server, as the error is retried until attempts are exhausted.
+		mock_server.expect(3).assert();
+
+		let error_response = ErrorResponse {
+			error_code: ErrorCode::NoSuchKeyException.into(),
+			message: "NoSuchKeyException".to_string(),
+		};
+		let mock_server = mockito::mock("POST", Matcher::Any)
+			.with_status(404)
+			.with_body(error_response.encode_to_vec())
+			.create();
+
+		let get_result = client.get_object(&get_request);
+		assert!(matches!(get_result.unwrap_err(), VssError::NoSuchKeyError { .. }));
+
+		// Verify only 1 request hit the server, as the error is not retried.
+		mock_server.expect(1).assert();
+	}
+
+	#[test]
+	fn test_blocking_wrapper() {
+		let base_url = mockito::server_url().to_string();
+		let vss_client = VssClientBuilder::new(base_url.clone(), retry_policy())
+			.store_id("wrapped_store".to_string())
+			.build()
+			.unwrap();
+		let client = VssClientBlockingWrapper::new(vss_client).unwrap();
+		assert_eq!(
+			format!("{:?}", client),
+			format!("VssClientBlockingWrapper {{ base_url: {:?}, .. }}", base_url)
+		);
+
+		// Requests are made by the wrapped client, e.g. using its default store id.
+		let key_value = KeyValue { key: "k1".to_string(), version: 2, value: b"k1v2".to_vec() };
+		let get_mock_server = mockito::mock("POST", GET_OBJECT_ENDPOINT)
+			.match_body(
+				GetObjectRequest { store_id: "wrapped_store".to_string(), key: "k1".to_string() }
+					.encode_to_vec(),
+			)
+			.with_status(200)
+			.with_body(GetObjectResponse { value: Some(key_value.clone()) }.encode_to_vec())
+			.create();
+		let get_request = GetObjectRequest { store_id: String::new(), key: "k1".to_string() };
+		assert_eq!(client.get_object(&get_request).unwrap().value, Some(key_value.clone()));
+
+		let put_request = |store_id: &str| PutObjectRequest {
+			store_id: store_id.to_string(),
+			global_version: None,
+			transaction_items: vec![key_value.clone()],
+			delete_items: vec![],
+		};
+		let put_mock_server = mockito::mock("POST", PUT_OBJECT_ENDPOINT)
+			.match_body(put_request("wrapped_store").encode_to_vec())
+			.with_status(200)
+			.with_body(PutObjectResponse {}.encode_to_vec())
+			.create();
+		assert_eq!(client.put_object(&put_request("")).unwrap(), PutObjectResponse {});
+
+		let delete_request = |store_id: &str| DeleteObjectRequest {
+			store_id: store_id.to_string(),
+			key_value: Some(key_value.clone()),
+		};
+		let delete_mock_server = mockito::mock("POST", DELETE_OBJECT_ENDPOINT)
+			.match_body(delete_request("wrapped_store").encode_to_vec())
+			.with_status(200)
+			.with_body(DeleteObjectResponse {}.encode_to_vec())
+			.create();
+		assert_eq!(client.delete_object(&delete_request("")).unwrap(), DeleteObjectResponse {});
+
+		let list_request = |store_id: &str| ListKeyVersionsRequest {
+			store_id: store_id.to_string(),
+			page_size: None,
+			page_token: None,
+			key_prefix: None,
+		};
+		let list_response = ListKeyVersionsResponse {
+			key_versions: vec![key_value.clone()],
+			next_page_token: None,
+			global_version: Some(3),
+		};
+		let list_mock_server = mockito::mock("POST", LIST_KEY_VERSIONS_ENDPOINT)
+			.match_body(list_request("wrapped_store").encode_to_vec())
+			.with_status(200)
+			.with_body(list_response.encode_to_vec())
+			.create();
+		assert_eq!(client.list_key_versions(&list_request("")).unwrap(), list_response);
+
+		for mock_server in [get_mock_server, put_mock_server, delete_mock_server, list_mock_server]
+		{
+			mock_server.assert();
+		}
+	}
+
+	fn retry_policy() -> impl RetryPolicy<E = VssError> {
+		ExponentialBackoffRetryPolicy::new(Duration::from_millis(1)).with_max_attempts(3)
+	}
+}
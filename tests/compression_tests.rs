@@ -0,0 +1,148 @@
+#[cfg(feature = "gzip")]
+mod compression_tests {
+	use flate2::read::GzDecoder;
+	use flate2::write::GzEncoder;
+	use flate2::Compression;
+	use mockito::{self, Matcher};
+	use prost::Message;
+	use std::io::{Read, Write};
+	use std::time::Duration;
+	use vss_client::client::VssClientBuilder;
+	use vss_client::compression::{CompressionLevel, CompressionMode};
+	use vss_client::error::VssError;
+	use vss_client::types::{
+		GetObjectRequest, GetObjectResponse, KeyValue, PutObjectRequest, PutObjectResponse,
+	};
+	use vss_client::util::retry::{ExponentialBackoffRetryPolicy, RetryPolicy};
+
+	const GET_OBJECT_ENDPOINT: &str = "/getObject";
+	const PUT_OBJECT_ENDPOINT: &str = "/putObjects";
+
+	fn gzip(data: &[u8]) -> Vec<u8> {
+		let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+		encoder.write_all(data).unwrap();
+		encoder.finish().unwrap()
+	}
+
+	fn gunzip(data: &[u8]) -> Vec<u8> {
+		let mut decompressed = Vec::new();
+		GzDecoder::new(data).read_to_end(&mut decompressed).unwrap();
+		decompressed
+	}
+
+	fn put_request(value: Vec<u8>) -> PutObjectRequest {
+		PutObjectRequest {
+			store_id: "store".to_string(),
+			global_version: None,
+			transaction_items: vec![KeyValue { key: "k1".to_string(), version: 0, value }],
+			delete_items: vec![],
+		}
+	}
+
+	#[tokio::test]
+	async fn test_gzip_compression() {
+		let base_url = mockito::server_url();
+		let get_request = GetObjectRequest { store_id: "store".to_string(), key: "k1".to_string() };
+		let mock_response = GetObjectResponse {
+			value: Some(KeyValue { key: "k1".to_string(), version: 2, value: b"k1v2".to_vec() }),
+		};
+
+		// The request is compressed, and the compressed response is decompressed.
+		let mock_server = mockito::mock("POST", GET_OBJECT_ENDPOINT)
+			.match_header("content-encoding", "gzip")
+			.match_header("accept-encoding", "gzip")
+			.with_status(200)
+			.with_header("content-encoding", "gzip")
+			.with_body(gzip(&mock_response.encode_to_vec()))
+			.create();
+
+		let client = VssClientBuilder::new(base_url, retry_policy())
+			.compression(CompressionMode::Gzip)
+			.compression_level(CompressionLevel::new(9).unwrap())
+			.build()
+			.unwrap();
+		let actual_result = client.get_object(&get_request).await.unwrap();
+		assert_eq!(actual_result, mock_response);
+
+		mock_server.expect(1).assert();
+	}
+
+	#[tokio::test]
+	async fn test_compressed_request_body() {
+		let base_url = mockito::server_url();
+		let request = put_request(vec![42u8; 4096]);
+		let compressed_body = gzip(&request.encode_to_vec());
+		assert_eq!(gunzip(&compressed_body), request.encode_to_vec());
+
+		let mock_server = mockito::mock("POST", PUT_OBJECT_ENDPOINT)
+			.match_header("content-encoding", "gzip")
+			.match_body(compressed_body)
+			.with_status(200)
+			.with_body(PutObjectResponse {}.encode_to_vec())
+			.create();
+
+		let client = VssClientBuilder::new(base_url, retry_policy())
+			.compression(CompressionMode::Auto)
+			.compression_level(CompressionLevel::default())
+			.build()
+			.unwrap();
+		client.put_object(&request).await.unwrap();
+
+		mock_server.expect(1).assert();
+	}
+
+	#[tokio::test]
+	async fn test_auto_compression_falls_back_when_unsupported() {
+		let base_url = mockito::server_url();
+		let request = put_request(vec![42u8; 4096]);
+
+		let unsupported_mock_server = mockito::mock("POST", PUT_OBJECT_ENDPOINT)
+			.match_header("content-encoding", "gzip")
+			.with_status(415)
+			.create();
+		let mock_server = mockito::mock("POST", PUT_OBJECT_ENDPOINT)
+			.match_header("content-encoding", Matcher::Missing)
+			.match_body(request.encode_to_vec())
+			.with_status(200)
+			.with_body(PutObjectResponse {}.encode_to_vec())
+			.expect(2)
+			.create();
+
+		let client = VssClientBuilder::new(base_url, retry_policy())
+			.compression(CompressionMode::Auto)
+			.build()
+			.unwrap();
+		client.put_object(&request).await.unwrap();
+		// Compression remains disabled for subsequent requests.
+		client.put_object(&request).await.unwrap();
+
+		unsupported_mock_server.expect(1).assert();
+		mock_server.assert();
+	}
+
+	#[tokio::test]
+	async fn test_small_requests_are_not_compressed_in_auto_mode() {
+		let base_url = mockito::server_url();
+		let request = put_request(b"small".to_vec());
+
+		let mock_server = mockito::mock("POST", PUT_OBJECT_ENDPOINT)
+			.match_header("content-encoding", Matcher::Missing)
+			.match_header("accept-encoding", "gzip")
+			.match_body(request.encode_to_vec())
+			.with_status(200)
+			.with_body(PutObjectResponse {}.encode_to_vec())
+			.create();
+
+		let client = VssClientBuilder::new(base_url, retry_policy())
+			.compression(CompressionMode::Auto)
+			.build()
+			.unwrap();
+		client.put_object(&request).await.unwrap();
+
+		mock_server.expect(1).assert();
+	}
+
+	fn retry_policy() -> impl RetryPolicy<E = VssError> {
+		ExponentialBackoffRetryPolicy::new(Duration::from_millis(1)).with_max_attempts(3)
+	}
+}
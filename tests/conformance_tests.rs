@@ -0,0 +1,171 @@
+#[cfg(feature = "test-utils")]
+mod conformance_tests {
+	use rand::distributions::Alphanumeric;
+	use rand::Rng;
+	use std::time::Duration;
+	use vss_client::client::{VssApi, VssClient};
+	use vss_client::error::VssError;
+	use vss_client::test_utils::InMemoryVssClient;
+	use vss_client::types::{
+		DeleteObjectRequest, GetObjectRequest, KeyValue, ListKeyVersionsRequest, PutObjectRequest,
+	};
+	use vss_client::util::retry::{ExponentialBackoffRetryPolicy, RetryPolicy};
+
+	// The URL of a VSS server to additionally run the suite against, if set.
+	const VSS_SERVER_URL_VAR: &str = "VSS_SERVER_URL";
+
+	fn key_value(key: &str, version: i64, value: &[u8]) -> KeyValue {
+		KeyValue { key: key.to_string(), version, value: value.to_vec() }
+	}
+
+	fn put_request(
+		store_id: &str, global_version: Option<i64>, transaction_items: Vec<KeyValue>,
+		delete_items: Vec<KeyValue>,
+	) -> PutObjectRequest {
+		PutObjectRequest {
+			store_id: store_id.to_string(),
+			global_version,
+			transaction_items,
+			delete_items,
+		}
+	}
+
+	async fn get<A: VssApi>(api: &A, store_id: &str, key: &str) -> Result<KeyValue, VssError> {
+		let request = GetObjectRequest { store_id: store_id.to_string(), key: key.to_string() };
+		Ok(api.get_object(&request).await?.value.unwrap())
+	}
+
+	async fn delete<A: VssApi>(api: &A, store_id: &str, key_value: KeyValue) {
+		let request =
+			DeleteObjectRequest { store_id: store_id.to_string(), key_value: Some(key_value) };
+		api.delete_object(&request).await.unwrap();
+	}
+
+	// Lists all keys with the given prefix, following the pagination. Returns the keys and the
+	// global version returned as part of the first page.
+	async fn list_all<A: VssApi>(
+		api: &A, store_id: &str, key_prefix: &str, page_size: i32,
+	) -> (Vec<String>, Option<i64>) {
+		let mut request = ListKeyVersionsRequest {
+			store_id: store_id.to_string(),
+			key_prefix: Some(key_prefix.to_string()),
+			page_size: Some(page_size),
+			page_token: None,
+		};
+		let mut keys = Vec::new();
+		let mut global_version = None;
+		loop {
+			let response = api.list_key_versions(&request).await.unwrap();
+			if request.page_token.is_none() {
+				global_version = response.global_version;
+			}
+			assert!(response.key_versions.len() <= page_size as usize);
+			assert!(response.key_versions.iter().all(|kv| kv.value.is_empty()));
+			keys.extend(response.key_versions.into_iter().map(|kv| kv.key));
+			match response.next_page_token {
+				Some(token) if !token.is_empty() => request.page_token = Some(token),
+				_ => break,
+			}
+		}
+		(keys, global_version)
+	}
+
+	// Exercises the semantics of a VSS server against the given `api`, operating on a fresh
+	// `store_id`.
+	async fn run_suite<A: VssApi>(api: &A, store_id: &str) {
+		// Missing keys are reported as such.
+		let result = get(api, store_id, "k1").await;
+		assert!(matches!(result.unwrap_err(), VssError::NoSuchKeyError { .. }));
+
+		// Key-level versioning.
+		let request = put_request(store_id, None, vec![key_value("k1", 0, b"k1v1")], vec![]);
+		api.put_object(&request).await.unwrap();
+		assert_eq!(get(api, store_id, "k1").await.unwrap(), key_value("k1", 1, b"k1v1"));
+		let result = api.put_object(&request).await;
+		assert!(matches!(result.unwrap_err(), VssError::ConflictError { .. }));
+		let request = put_request(store_id, None, vec![key_value("k1", 1, b"k1v2")], vec![]);
+		api.put_object(&request).await.unwrap();
+		assert_eq!(get(api, store_id, "k1").await.unwrap(), key_value("k1", 2, b"k1v2"));
+		let request = put_request(store_id, None, vec![key_value("k1", -1, b"k1v3")], vec![]);
+		api.put_object(&request).await.unwrap();
+		assert_eq!(get(api, store_id, "k1").await.unwrap().value, b"k1v3".to_vec());
+
+		// Transactions are all-or-nothing.
+		let request = put_request(
+			store_id,
+			None,
+			vec![key_value("k2", 0, b"k2v1"), key_value("k3", 1, b"k3v1")],
+			vec![],
+		);
+		let result = api.put_object(&request).await;
+		assert!(matches!(result.unwrap_err(), VssError::ConflictError { .. }));
+		let result = get(api, store_id, "k2").await;
+		assert!(matches!(result.unwrap_err(), VssError::NoSuchKeyError { .. }));
+		let request = put_request(
+			store_id,
+			None,
+			vec![key_value("k2", 0, b"k2v1")],
+			vec![key_value("k4", -1, b"")],
+		);
+		let result = api.put_object(&request).await;
+		assert!(matches!(result.unwrap_err(), VssError::ConflictError { .. }));
+
+		// Global versioning.
+		let (_, global_version) = list_all(api, store_id, "", 10).await;
+		let global_version = global_version.unwrap();
+		let request =
+			put_request(store_id, Some(global_version), vec![key_value("k2", 0, b"k2v1")], vec![]);
+		api.put_object(&request).await.unwrap();
+		let request =
+			put_request(store_id, Some(global_version), vec![key_value("k3", 0, b"k3v1")], vec![]);
+		let result = api.put_object(&request).await;
+		assert!(matches!(result.unwrap_err(), VssError::ConflictError { .. }));
+		let (_, next_global_version) = list_all(api, store_id, "", 10).await;
+		assert_eq!(next_global_version, Some(global_version + 1));
+
+		// Deletes are conditional, but idempotent.
+		delete(api, store_id, key_value("k2", 2, b"")).await;
+		assert!(get(api, store_id, "k2").await.is_ok());
+		delete(api, store_id, key_value("k2", 1, b"")).await;
+		delete(api, store_id, key_value("k2", 1, b"")).await;
+		let result = get(api, store_id, "k2").await;
+		assert!(matches!(result.unwrap_err(), VssError::NoSuchKeyError { .. }));
+		let request = put_request(store_id, None, vec![], vec![key_value("k1", -1, b"")]);
+		api.put_object(&request).await.unwrap();
+		let result = get(api, store_id, "k1").await;
+		assert!(matches!(result.unwrap_err(), VssError::NoSuchKeyError { .. }));
+
+		// Listing by key prefix, across pages.
+		let mut expected_keys = Vec::new();
+		for i in 0..5 {
+			let key = format!("list/{}", i);
+			let request = put_request(store_id, None, vec![key_value(&key, 0, b"value")], vec![]);
+			api.put_object(&request).await.unwrap();
+			expected_keys.push(key);
+		}
+		let request = put_request(store_id, None, vec![key_value("other", 0, b"value")], vec![]);
+		api.put_object(&request).await.unwrap();
+		let (mut keys, _) = list_all(api, store_id, "list/", 2).await;
+		keys.sort();
+		assert_eq!(keys, expected_keys);
+	}
+
+	#[tokio::test]
+	async fn in_memory_client_conforms() {
+		run_suite(&InMemoryVssClient::new(), "store").await;
+	}
+
+	#[tokio::test]
+	async fn vss_client_conforms() {
+		let base_url = match std::env::var(VSS_SERVER_URL_VAR) {
+			Ok(base_url) => base_url,
+			Err(_) => return,
+		};
+		let retry_policy =
+			ExponentialBackoffRetryPolicy::new(Duration::from_millis(10)).with_max_attempts(3);
+		let client = VssClient::new(base_url, retry_policy);
+		let suffix: String =
+			rand::thread_rng().sample_iter(&Alphanumeric).take(16).map(char::from).collect();
+		run_suite(&client, &format!("conformance_{}", suffix)).await;
+	}
+}
@@ -11,7 +11,7 @@ mod lnurl_auth_jwt_tests {
 	use vss_client::headers::LnurlAuthToJwtProvider;
 	use vss_client::headers::VssHeaderProvider;
 
-	const APPLICATION_JSON: &'static str = "application/json";
+	const APPLICATION_JSON: &str = "application/json";
 
 	fn lnurl_auth_response(jwt: &str) -> String {
 		json!({
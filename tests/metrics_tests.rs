@@ -0,0 +1,177 @@
+#[cfg(feature = "metrics")]
+mod metrics_tests {
+	use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+	use metrics_util::{CompositeKey, MetricKind};
+	use mockito::{self, Matcher};
+	use prost::Message;
+	use std::time::Duration;
+	use vss_client::client::VssClient;
+	use vss_client::error::VssError;
+	use vss_client::metrics::{
+		IN_FLIGHT_REQUESTS, REQUESTS_TOTAL, REQUEST_DURATION_MS, RETRIES_TOTAL,
+	};
+	use vss_client::types::{
+		ErrorCode, ErrorResponse, GetObjectRequest, GetObjectResponse, KeyValue,
+	};
+	use vss_client::util::retry::{ExponentialBackoffRetryPolicy, RetryPolicy};
+
+	const GET_OBJECT_ENDPOINT: &str = "/getObject";
+
+	// Returns the value of the metric with the given kind, name and labels, if recorded.
+	fn metric<'a>(
+		snapshot: &'a [(CompositeKey, DebugValue)], kind: MetricKind, name: &str,
+		labels: &[(&str, &str)],
+	) -> Option<&'a DebugValue> {
+		snapshot.iter().find_map(|(key, value)| {
+			let key_labels: Vec<(&str, &str)> =
+				key.key().labels().map(|label| (label.key(), label.value())).collect();
+			let matches = key.kind() == kind && key.key().name() == name && key_labels == labels;
+			if matches {
+				Some(value)
+			} else {
+				None
+			}
+		})
+	}
+
+	#[test]
+	fn test_operations_are_measured() {
+		let recorder = DebuggingRecorder::new();
+		let snapshotter = recorder.snapshotter();
+
+		let base_url = mockito::server_url();
+		let get_request = GetObjectRequest { store_id: "store".to_string(), key: "k1".to_string() };
+		let mock_response = GetObjectResponse {
+			value: Some(KeyValue { key: "k1".to_string(), version: 2, value: b"k1v2".to_vec() }),
+		};
+		let error_response = ErrorResponse {
+			error_code: ErrorCode::InternalServerException.into(),
+			message: "InternalServerException".to_string(),
+		};
+		// The first attempt fails and is retried.
+		let failing_mock_server = mockito::mock("POST", GET_OBJECT_ENDPOINT)
+			.with_status(500)
+			.with_body(error_response.encode_to_vec())
+			.expect(1)
+			.create();
+		let mock_server = mockito::mock("POST", GET_OBJECT_ENDPOINT)
+			.with_status(200)
+			.with_body(mock_response.encode_to_vec())
+			.expect(1)
+			.create();
+		let no_such_key_response = ErrorResponse {
+			error_code: ErrorCode::NoSuchKeyException.into(),
+			message: "NoSuchKeyException".to_string(),
+		};
+		let missing_mock_server = mockito::mock("POST", Matcher::Any)
+			.with_status(404)
+			.with_body(no_such_key_response.encode_to_vec())
+			.create();
+
+		metrics::with_local_recorder(&recorder, || {
+			let runtime =
+				tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+			runtime.block_on(async {
+				let client = VssClient::new(base_url, retry_policy());
+				client.get_object(&get_request).await.unwrap();
+				let result = client.get_object(&get_request).await;
+				assert!(matches!(result.unwrap_err(), VssError::NoSuchKeyError { .. }));
+			});
+		});
+		failing_mock_server.assert();
+		mock_server.assert();
+		missing_mock_server.expect(1).assert();
+
+		// Taking a snapshot drains the recorded histograms, hence only take a single one.
+		let snapshot: Vec<(CompositeKey, DebugValue)> = snapshotter
+			.snapshot()
+			.into_vec()
+			.into_iter()
+			.map(|(key, _, _, value)| (key, value))
+			.collect();
+		let success_labels = [("operation", "get_object"), ("status", "success")];
+		assert_eq!(
+			metric(&snapshot, MetricKind::Counter, REQUESTS_TOTAL, &success_labels),
+			Some(&DebugValue::Counter(1))
+		);
+		let failure_labels = [("operation", "get_object"), ("status", "no_such_key")];
+		assert_eq!(
+			metric(&snapshot, MetricKind::Counter, REQUESTS_TOTAL, &failure_labels),
+			Some(&DebugValue::Counter(1))
+		);
+		assert_eq!(
+			metric(&snapshot, MetricKind::Counter, RETRIES_TOTAL, &[]),
+			Some(&DebugValue::Counter(1))
+		);
+		let durations = metric(
+			&snapshot,
+			MetricKind::Histogram,
+			REQUEST_DURATION_MS,
+			&[("operation", "get_object")],
+		);
+		assert!(
+			matches!(durations, Some(DebugValue::Histogram(durations)) if durations.len() == 2)
+		);
+		// No requests remain in flight.
+		let in_flight = metric(&snapshot, MetricKind::Gauge, IN_FLIGHT_REQUESTS, &[]);
+		assert!(matches!(in_flight, Some(DebugValue::Gauge(value)) if value.into_inner() == 0.0));
+	}
+
+	#[cfg(all(feature = "cache", feature = "test-utils"))]
+	#[test]
+	fn test_cache_lookups_are_counted() {
+		use vss_client::cache::CachedVssClient;
+		use vss_client::client::VssApi;
+		use vss_client::metrics::{CACHE_HITS_TOTAL, CACHE_MISSES_TOTAL};
+		use vss_client::test_utils::InMemoryVssClient;
+		use vss_client::types::PutObjectRequest;
+
+		let recorder = DebuggingRecorder::new();
+		let snapshotter = recorder.snapshotter();
+
+		metrics::with_local_recorder(&recorder, || {
+			let runtime =
+				tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+			runtime.block_on(async {
+				let client =
+					CachedVssClient::new(InMemoryVssClient::new(), 10, Duration::from_secs(60));
+				let put_request = PutObjectRequest {
+					store_id: "store".to_string(),
+					global_version: None,
+					// Written non-conditionally, such that the value isn't cached by the write.
+					transaction_items: vec![KeyValue {
+						key: "k1".to_string(),
+						version: -1,
+						value: b"k1v1".to_vec(),
+					}],
+					delete_items: vec![],
+				};
+				client.put_object(&put_request).await.unwrap();
+				let get_request =
+					GetObjectRequest { store_id: "store".to_string(), key: "k1".to_string() };
+				for _ in 0..3 {
+					client.get_object(&get_request).await.unwrap();
+				}
+			});
+		});
+
+		let snapshot: Vec<(CompositeKey, DebugValue)> = snapshotter
+			.snapshot()
+			.into_vec()
+			.into_iter()
+			.map(|(key, _, _, value)| (key, value))
+			.collect();
+		assert_eq!(
+			metric(&snapshot, MetricKind::Counter, CACHE_MISSES_TOTAL, &[]),
+			Some(&DebugValue::Counter(1))
+		);
+		assert_eq!(
+			metric(&snapshot, MetricKind::Counter, CACHE_HITS_TOTAL, &[]),
+			Some(&DebugValue::Counter(2))
+		);
+	}
+
+	fn retry_policy() -> impl RetryPolicy<E = VssError> {
+		ExponentialBackoffRetryPolicy::new(Duration::from_millis(1)).with_max_attempts(3)
+	}
+}
@@ -0,0 +1,69 @@
+#[cfg(feature = "opentelemetry")]
+mod opentelemetry_tests {
+	use mockito::{self, Matcher};
+	use opentelemetry::context::FutureExt;
+	use opentelemetry::trace::{
+		SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState,
+	};
+	use opentelemetry::Context;
+	use opentelemetry_sdk::propagation::TraceContextPropagator;
+	use prost::Message;
+	use std::str::FromStr;
+	use std::time::Duration;
+	use vss_client::client::VssClientBuilder;
+	use vss_client::error::VssError;
+	use vss_client::types::{GetObjectRequest, GetObjectResponse, KeyValue};
+	use vss_client::util::retry::{ExponentialBackoffRetryPolicy, RetryPolicy};
+
+	const GET_OBJECT_ENDPOINT: &str = "/getObject";
+
+	#[tokio::test]
+	async fn test_trace_context_is_propagated() {
+		opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+
+		let base_url = mockito::server_url();
+		let get_request = GetObjectRequest { store_id: "store".to_string(), key: "k1".to_string() };
+		let mock_response = GetObjectResponse {
+			value: Some(KeyValue { key: "k1".to_string(), version: 2, value: b"k1v2".to_vec() }),
+		};
+		let span_context = SpanContext::new(
+			TraceId::from_hex("4bf92f3577b34da6a3ce929d0e0e4736").unwrap(),
+			SpanId::from_hex("00f067aa0ba902b7").unwrap(),
+			TraceFlags::SAMPLED,
+			true,
+			TraceState::from_str("vendor=value").unwrap(),
+		);
+		let context = Context::new().with_remote_span_context(span_context);
+
+		let propagating_mock_server = mockito::mock("POST", GET_OBJECT_ENDPOINT)
+			.match_header("traceparent", "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01")
+			.match_header("tracestate", "vendor=value")
+			.with_status(200)
+			.with_body(mock_response.encode_to_vec())
+			.create();
+		let client = VssClientBuilder::new(base_url.clone(), retry_policy())
+			.otel_propagation(true)
+			.build()
+			.unwrap();
+		let actual_result =
+			client.get_object(&get_request).with_context(context.clone()).await.unwrap();
+		assert_eq!(actual_result, mock_response);
+		propagating_mock_server.expect(1).assert();
+
+		// Without propagation enabled, no context headers are sent.
+		let mock_server = mockito::mock("POST", GET_OBJECT_ENDPOINT)
+			.match_header("traceparent", Matcher::Missing)
+			.match_header("tracestate", Matcher::Missing)
+			.with_status(200)
+			.with_body(mock_response.encode_to_vec())
+			.create();
+		let client = VssClientBuilder::new(base_url, retry_policy()).build().unwrap();
+		let actual_result = client.get_object(&get_request).with_context(context).await.unwrap();
+		assert_eq!(actual_result, mock_response);
+		mock_server.expect(1).assert();
+	}
+
+	fn retry_policy() -> impl RetryPolicy<E = VssError> {
+		ExponentialBackoffRetryPolicy::new(Duration::from_millis(1)).with_max_attempts(3)
+	}
+}
@@ -2,11 +2,13 @@
 mod retry_tests {
 	use std::io;
 	use std::sync::atomic::{AtomicU32, Ordering};
-	use std::sync::Arc;
+	use std::sync::{Arc, Mutex};
 	use std::time::Duration;
 
 	use vss_client::error::VssError;
-	use vss_client::util::retry::{retry, ExponentialBackoffRetryPolicy, RetryPolicy};
+	use vss_client::util::retry::{
+		retry, ExponentialBackoffRetryPolicy, RetryContext, RetryPolicy,
+	};
 
 	#[tokio::test]
 	async fn test_async_retry() {
@@ -28,7 +30,11 @@ mod retry_tests {
 			async move {
 				let attempts_made = count.fetch_add(1, Ordering::SeqCst);
 				if attempts_made < max_attempts - 1 {
-					return Err(VssError::InternalServerError("Failure".to_string()));
+					return Err(VssError::InternalServerError {
+						message: "Failure".to_string(),
+						status: None,
+						request_id: None,
+					});
 				}
 				tokio::time::sleep(Duration::from_millis(100)).await;
 				Ok(42)
@@ -46,7 +52,11 @@ mod retry_tests {
 			async move {
 				count.fetch_add(1, Ordering::SeqCst);
 				tokio::time::sleep(Duration::from_millis(100)).await;
-				Err::<(), VssError>(VssError::InternalServerError("Failed".to_string()))
+				Err::<(), VssError>(VssError::InternalServerError {
+					message: "Failed".to_string(),
+					status: None,
+					request_id: None,
+				})
 			}
 		};
 
@@ -88,7 +98,11 @@ mod retry_tests {
 			async move {
 				count.fetch_add(1, Ordering::SeqCst);
 				tokio::time::sleep(Duration::from_millis(100)).await;
-				Err::<(), VssError>(VssError::InternalServerError("Failed".to_string()))
+				Err::<(), VssError>(VssError::InternalServerError {
+					message: "Failed".to_string(),
+					status: None,
+					request_id: None,
+				})
 			}
 		};
 
@@ -96,4 +110,111 @@ mod retry_tests {
 		assert!(failed_result.is_err());
 		assert_eq!(call_count.load(Ordering::SeqCst), 2);
 	}
+
+	#[tokio::test]
+	async fn test_retry_capped_by_max_elapsed_time() {
+		let retry_policy = ExponentialBackoffRetryPolicy::new(Duration::from_millis(10))
+			.with_max_attempts(10)
+			.with_max_elapsed_time(Duration::from_millis(150));
+
+		let call_count = Arc::new(AtomicU32::new(0));
+		let count = call_count.clone();
+		let failing_async_function = move || {
+			let count = count.clone();
+			async move {
+				count.fetch_add(1, Ordering::SeqCst);
+				tokio::time::sleep(Duration::from_millis(100)).await;
+				Err::<(), VssError>(VssError::InternalServerError {
+					message: "Failed".to_string(),
+					status: None,
+					request_id: None,
+				})
+			}
+		};
+
+		// The time taken by the operation counts towards the budget, so retrying stops after the
+		// second attempt even though neither the accumulated delay nor max attempts were exhausted.
+		let failed_result = retry(failing_async_function, &retry_policy).await;
+		assert!(failed_result.is_err());
+		assert_eq!(call_count.load(Ordering::SeqCst), 2);
+	}
+
+	struct LinearBackoffRetryPolicy;
+
+	impl RetryPolicy for LinearBackoffRetryPolicy {
+		type E = io::Error;
+		fn next_delay(&self, context: &RetryContext<Self::E>) -> Option<Duration> {
+			Some(Duration::from_millis(5) * context.attempts_made())
+		}
+	}
+
+	#[tokio::test]
+	async fn test_custom_retry_policy_capped_by_max_attempts() {
+		let retry_policy = LinearBackoffRetryPolicy.with_max_attempts(5);
+
+		let call_count = Arc::new(AtomicU32::new(0));
+		let count = call_count.clone();
+		let failing_async_function = move || {
+			let count = count.clone();
+			async move {
+				count.fetch_add(1, Ordering::SeqCst);
+				Err::<(), io::Error>(io::Error::new(io::ErrorKind::InvalidData, "Failure"))
+			}
+		};
+
+		let failed_result = retry(failing_async_function, &retry_policy).await;
+		assert!(failed_result.is_err());
+		assert_eq!(call_count.load(Ordering::SeqCst), 5);
+	}
+
+	#[tokio::test]
+	async fn test_retry_skipped_on_filtered_errors() {
+		// Retries everything except conflicts and an application-level `507` of the server.
+		let retry_policy = ExponentialBackoffRetryPolicy::new(Duration::from_millis(1))
+			.with_max_attempts(5)
+			.skip_retry_on_error(|e: &VssError| {
+				matches!(e, VssError::ConflictError { .. }) || e.http_status() == Some(507)
+			});
+		let errors = |terminal_error: VssError| {
+			vec![
+				VssError::InternalServerError {
+					message: "Unavailable".to_string(),
+					status: None,
+					request_id: None,
+				},
+				terminal_error,
+			]
+		};
+
+		for terminal_error in [
+			VssError::ConflictError {
+				message: "Version mismatch".to_string(),
+				key: Some("k1".to_string()),
+				expected_version: Some(3),
+				current_version: Some(4),
+				request_id: None,
+			},
+			VssError::InternalServerError {
+				message: "Insufficient storage".to_string(),
+				status: Some(507),
+				request_id: None,
+			},
+		] {
+			let expected_error = terminal_error.to_string();
+			let remaining_errors = Arc::new(Mutex::new(errors(terminal_error)));
+			let call_count = Arc::new(AtomicU32::new(0));
+			let (count, remaining) = (call_count.clone(), remaining_errors.clone());
+			let failing_async_function = move || {
+				count.fetch_add(1, Ordering::SeqCst);
+				let error = remaining.lock().unwrap().remove(0);
+				async move { Err::<(), VssError>(error) }
+			};
+
+			// The retryable error is retried, while the filtered one fails right away as returned.
+			let error = retry(failing_async_function, &retry_policy).await.unwrap_err();
+			assert_eq!(call_count.load(Ordering::SeqCst), 2);
+			assert_eq!(error.to_string(), expected_error);
+			assert!(remaining_errors.lock().unwrap().is_empty());
+		}
+	}
 }
@@ -0,0 +1,156 @@
+#[cfg(any(feature = "rustls-tls", feature = "default-tls"))]
+mod tls_tests {
+	use prost::Message;
+	use std::sync::Arc;
+	use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+	use tokio::net::TcpListener;
+	use tokio_rustls::rustls::server::AllowAnyAuthenticatedClient;
+	use tokio_rustls::rustls::{Certificate, PrivateKey, RootCertStore, ServerConfig};
+	use tokio_rustls::TlsAcceptor;
+	use vss_client::client::VssClientBuilder;
+	use vss_client::error::VssError;
+	use vss_client::types::{GetObjectRequest, GetObjectResponse, KeyValue};
+	use vss_client::util::retry::NoRetryPolicy;
+
+	// The root certificate of the CA which issued the server certificate for `localhost`.
+	const CA_CERT_PEM: &[u8] = include_bytes!("data/ca_cert.pem");
+	const SERVER_CERT_PEM: &[u8] = include_bytes!("data/server_cert.pem");
+	const SERVER_KEY_PEM: &[u8] = include_bytes!("data/server_key.pem");
+	// A client certificate issued by the same CA.
+	const CLIENT_CERT_PEM: &[u8] = include_bytes!("data/tls_client_cert.pem");
+	const CLIENT_KEY_PEM: &[u8] = include_bytes!("data/tls_client_key.pem");
+
+	fn get_request() -> GetObjectRequest {
+		GetObjectRequest { store_id: "store".to_string(), key: "k1".to_string() }
+	}
+
+	fn get_response() -> GetObjectResponse {
+		GetObjectResponse {
+			value: Some(KeyValue { key: "k1".to_string(), version: 2, value: b"k1v2".to_vec() }),
+		}
+	}
+
+	// Spawns a TLS server responding to all requests with `get_response`, returning its base url.
+	// If `require_client_cert` is set, clients need to present a certificate issued by the CA.
+	async fn spawn_tls_server(require_client_cert: bool) -> String {
+		let certs = rustls_pemfile::certs(&mut &SERVER_CERT_PEM[..]).unwrap();
+		let mut keys = rustls_pemfile::pkcs8_private_keys(&mut &SERVER_KEY_PEM[..]).unwrap();
+		let config = ServerConfig::builder().with_safe_defaults();
+		let config = if require_client_cert {
+			let mut roots = RootCertStore::empty();
+			for cert in rustls_pemfile::certs(&mut &CA_CERT_PEM[..]).unwrap() {
+				roots.add(&Certificate(cert)).unwrap();
+			}
+			config.with_client_cert_verifier(AllowAnyAuthenticatedClient::new(roots).boxed())
+		} else {
+			config.with_no_client_auth()
+		};
+		let config = config
+			.with_single_cert(
+				certs.into_iter().map(Certificate).collect(),
+				PrivateKey(keys.remove(0)),
+			)
+			.unwrap();
+		let acceptor = TlsAcceptor::from(Arc::new(config));
+		let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let base_url = format!("https://localhost:{}", listener.local_addr().unwrap().port());
+
+		tokio::spawn(async move {
+			loop {
+				let (stream, _) = listener.accept().await.unwrap();
+				// Handshakes fail if either side rejects the other's certificate.
+				let stream = match acceptor.accept(stream).await {
+					Ok(stream) => stream,
+					Err(_) => continue,
+				};
+				let mut reader = BufReader::new(stream);
+				let mut line = String::new();
+				let mut content_length = 0;
+				while reader.read_line(&mut line).await.unwrap() > 2 {
+					if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+						content_length = value.trim().parse().unwrap();
+					}
+					line.clear();
+				}
+				let mut body = vec![0; content_length];
+				reader.read_exact(&mut body).await.unwrap();
+
+				let response_body = get_response().encode_to_vec();
+				let header = format!(
+					"HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+					response_body.len()
+				);
+				let stream = reader.get_mut();
+				stream.write_all(header.as_bytes()).await.unwrap();
+				stream.write_all(&response_body).await.unwrap();
+				stream.shutdown().await.unwrap();
+			}
+		});
+		base_url
+	}
+
+	#[tokio::test]
+	async fn test_trusts_added_root_certificate() {
+		let base_url = spawn_tls_server(false).await;
+		let client = VssClientBuilder::new(base_url, NoRetryPolicy::new())
+			.add_root_certificate(CA_CERT_PEM)
+			.build()
+			.unwrap();
+		assert_eq!(client.get_object(&get_request()).await.unwrap(), get_response());
+	}
+
+	#[tokio::test]
+	async fn test_rejects_untrusted_certificate() {
+		let base_url = spawn_tls_server(false).await;
+		let client = VssClientBuilder::new(base_url, NoRetryPolicy::new()).build().unwrap();
+		let result = client.get_object(&get_request()).await;
+		assert!(matches!(result, Err(VssError::InternalError { .. })));
+	}
+
+	#[tokio::test]
+	async fn test_accepts_invalid_certs_if_enabled() {
+		let base_url = spawn_tls_server(false).await;
+		let client = VssClientBuilder::new(base_url, NoRetryPolicy::new())
+			.danger_accept_invalid_certs(true)
+			.build()
+			.unwrap();
+		assert_eq!(client.get_object(&get_request()).await.unwrap(), get_response());
+
+		// It can't be combined with a pre-configured HTTP client.
+		let result = VssClientBuilder::new("https://localhost".to_string(), NoRetryPolicy::new())
+			.http_client(reqwest::Client::new())
+			.danger_accept_invalid_certs(true)
+			.build();
+		assert!(matches!(result, Err(VssError::InvalidArgumentError(..))));
+	}
+
+	#[tokio::test]
+	async fn test_mutual_tls() {
+		let base_url = spawn_tls_server(true).await;
+		let client = VssClientBuilder::new(base_url.clone(), NoRetryPolicy::new())
+			.add_root_certificate(CA_CERT_PEM)
+			.client_certificate(CLIENT_CERT_PEM, CLIENT_KEY_PEM)
+			.build()
+			.unwrap();
+		assert_eq!(client.get_object(&get_request()).await.unwrap(), get_response());
+
+		// Without a client certificate, or with one not issued by the CA, the server rejects the
+		// connection.
+		let client = VssClientBuilder::new(base_url.clone(), NoRetryPolicy::new())
+			.add_root_certificate(CA_CERT_PEM)
+			.build()
+			.unwrap();
+		let result = client.get_object(&get_request()).await;
+		assert!(matches!(result, Err(VssError::InternalError { .. })));
+		let client = VssClientBuilder::new(base_url, NoRetryPolicy::new())
+			.add_root_certificate(CA_CERT_PEM)
+			.client_certificate(
+				include_bytes!("data/client_cert.pem"),
+				include_bytes!("data/client_key.pem"),
+			)
+			.build()
+			.unwrap();
+		let result = client.get_object(&get_request()).await;
+		assert!(matches!(result, Err(VssError::InternalError { .. })));
+	}
+}
@@ -0,0 +1,191 @@
+#[cfg(feature = "tracing")]
+mod tracing_tests {
+	use mockito::{self, Matcher};
+	use prost::Message;
+	use std::collections::HashMap;
+	use std::fmt::Debug;
+	use std::sync::atomic::{AtomicU64, Ordering};
+	use std::sync::{Arc, Mutex};
+	use std::time::Duration;
+	use tracing::field::{Field, Visit};
+	use tracing::span::{Attributes, Id, Record};
+	use tracing::{Event, Metadata, Subscriber};
+	use tracing_core::span::Current;
+	use vss_client::client::{RequestOptions, VssClient};
+	use vss_client::error::VssError;
+	use vss_client::headers::FixedHeaders;
+	use vss_client::types::{
+		ErrorCode, ErrorResponse, GetObjectRequest, KeyValue, PutObjectRequest, PutObjectResponse,
+	};
+	use vss_client::util::retry::{ExponentialBackoffRetryPolicy, RetryPolicy};
+
+	struct RecordedSpan {
+		metadata: &'static Metadata<'static>,
+		fields: HashMap<String, String>,
+	}
+
+	struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+	impl<'a> Visit for FieldVisitor<'a> {
+		fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+			self.0.insert(field.name().to_string(), format!("{:?}", value));
+		}
+	}
+
+	// A minimal subscriber recording all spans and their fields.
+	#[derive(Clone, Default)]
+	struct RecordingSubscriber {
+		next_id: Arc<AtomicU64>,
+		spans: Arc<Mutex<HashMap<u64, RecordedSpan>>>,
+		events: Arc<Mutex<Vec<HashMap<String, String>>>>,
+		current: Arc<Mutex<Vec<u64>>>,
+	}
+
+	impl Subscriber for RecordingSubscriber {
+		fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+			true
+		}
+
+		fn new_span(&self, attributes: &Attributes<'_>) -> Id {
+			let id = self.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+			let mut span = RecordedSpan { metadata: attributes.metadata(), fields: HashMap::new() };
+			attributes.record(&mut FieldVisitor(&mut span.fields));
+			self.spans.lock().unwrap().insert(id, span);
+			Id::from_u64(id)
+		}
+
+		fn record(&self, span: &Id, values: &Record<'_>) {
+			let mut spans = self.spans.lock().unwrap();
+			let span = spans.get_mut(&span.into_u64()).unwrap();
+			values.record(&mut FieldVisitor(&mut span.fields));
+		}
+
+		fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+		fn event(&self, event: &Event<'_>) {
+			// Only record events of this crate, rather than of its dependencies.
+			if !event.metadata().target().starts_with("vss_client") {
+				return;
+			}
+			let mut fields = HashMap::new();
+			event.record(&mut FieldVisitor(&mut fields));
+			self.events.lock().unwrap().push(fields);
+		}
+
+		fn enter(&self, span: &Id) {
+			self.current.lock().unwrap().push(span.into_u64());
+		}
+
+		fn exit(&self, _span: &Id) {
+			self.current.lock().unwrap().pop();
+		}
+
+		fn current_span(&self) -> Current {
+			match self.current.lock().unwrap().last() {
+				Some(id) => {
+					Current::new(Id::from_u64(*id), self.spans.lock().unwrap()[id].metadata)
+				},
+				None => Current::none(),
+			}
+		}
+	}
+
+	#[tokio::test]
+	async fn test_operations_are_traced() {
+		let base_url = mockito::server_url();
+		let error_response = ErrorResponse {
+			error_code: ErrorCode::InternalServerException.into(),
+			message: "InternalServerException".to_string(),
+		};
+		let mock_server = mockito::mock("POST", Matcher::Any)
+			.with_status(500)
+			.with_body(error_response.encode_to_vec())
+			.create();
+
+		let subscriber = RecordingSubscriber::default();
+		let _guard = tracing::subscriber::set_default(subscriber.clone());
+
+		let client = VssClient::new(base_url.clone(), retry_policy());
+		let get_request = GetObjectRequest { store_id: "store".to_string(), key: "k1".to_string() };
+		let options = RequestOptions::new().request_id("request-1".to_string());
+		let result = client.get_object_with_options(&get_request, &options).await;
+		assert!(matches!(result.unwrap_err(), VssError::InternalServerError { .. }));
+		mock_server.expect(3).assert();
+
+		let spans = subscriber.spans.lock().unwrap();
+		let span = spans.values().find(|span| span.metadata.name() == "vss.get_object").unwrap();
+		assert_eq!(span.fields["store_id"], "store");
+		assert_eq!(span.fields["key"], "Some(\"k1\")");
+		assert_eq!(span.fields["http.url"], format!("\"{}/getObject\"", base_url));
+		assert_eq!(span.fields["http.request_id"], "\"request-1\"");
+		assert_eq!(span.fields["http.status_code"], "500");
+		assert_eq!(span.fields["retry.attempt"], "3");
+
+		// Both the retries and the final failure are reported as events.
+		let events = subscriber.events.lock().unwrap();
+		let messages: Vec<&str> = events.iter().map(|e| e["message"].as_str()).collect();
+		assert_eq!(
+			messages,
+			vec![
+				"Retrying failed attempt",
+				"Retrying failed attempt",
+				"Retry attempts exhausted",
+				"VSS request failed"
+			]
+		);
+		assert_eq!(events[0]["operation"], "\"vss.get_object\"");
+		assert_eq!(events[0]["attempt"], "1");
+		assert_eq!(events[1]["attempt"], "2");
+		assert!(events.iter().take(2).all(|event| event.contains_key("delay")));
+		assert_eq!(events[2]["attempts"], "3");
+		assert!(events[2]["error"].contains("InternalServerException"));
+	}
+
+	#[tokio::test]
+	async fn test_sensitive_data_is_not_traced() {
+		let base_url = mockito::server_url();
+		let mock_server = mockito::mock("POST", "/putObjects")
+			.with_status(200)
+			.with_body(PutObjectResponse {}.encode_to_vec())
+			.create();
+
+		let subscriber = RecordingSubscriber::default();
+		let _guard = tracing::subscriber::set_default(subscriber.clone());
+
+		let headers =
+			HashMap::from([("Authorization".to_string(), "Bearer secret-token".to_string())]);
+		let client = VssClient::new_with_headers(
+			base_url,
+			retry_policy(),
+			Arc::new(FixedHeaders::new(headers)),
+		);
+		let put_request = PutObjectRequest {
+			store_id: "store".to_string(),
+			global_version: None,
+			transaction_items: vec![
+				KeyValue { key: "k1".to_string(), version: 0, value: b"secret-value".to_vec() },
+				KeyValue { key: "k2".to_string(), version: 0, value: b"secret-value".to_vec() },
+			],
+			delete_items: vec![],
+		};
+		client.put_object(&put_request).await.unwrap();
+		mock_server.expect(1).assert();
+
+		let spans = subscriber.spans.lock().unwrap();
+		let span = spans.values().find(|span| span.metadata.name() == "vss.put_object").unwrap();
+		assert_eq!(span.fields["key_count"], "2");
+		assert_eq!(span.fields["http.request_size"], put_request.encoded_len().to_string());
+		assert_eq!(span.fields["http.response_size"], "0");
+
+		// Neither values nor headers are recorded.
+		let events = subscriber.events.lock().unwrap();
+		let recorded_fields = spans.values().map(|span| &span.fields).chain(events.iter());
+		for fields in recorded_fields {
+			assert!(fields.values().all(|value| !value.contains("secret")), "{:?}", fields);
+		}
+	}
+
+	fn retry_policy() -> impl RetryPolicy<E = VssError> {
+		ExponentialBackoffRetryPolicy::new(Duration::from_millis(1)).with_max_attempts(3)
+	}
+}
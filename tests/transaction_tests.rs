@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod transaction_tests {
+	use mockito::{self, Matcher};
+	use prost::Message;
+	use std::time::Duration;
+	use vss_client::client::VssClient;
+	use vss_client::error::VssError;
+	use vss_client::transaction::VssTransaction;
+	use vss_client::types::{
+		ErrorCode, ErrorResponse, KeyValue, PutObjectRequest, PutObjectResponse,
+	};
+	use vss_client::util::retry::{ExponentialBackoffRetryPolicy, RetryPolicy};
+
+	const PUT_OBJECT_ENDPOINT: &str = "/putObjects";
+
+	fn client(base_url: String) -> VssClient<impl RetryPolicy<E = VssError>> {
+		let retry_policy =
+			ExponentialBackoffRetryPolicy::new(Duration::from_millis(1)).with_max_attempts(1);
+		VssClient::new(base_url, retry_policy)
+	}
+
+	#[tokio::test]
+	async fn test_commit() {
+		let expected_request = PutObjectRequest {
+			store_id: "commit_store".to_string(),
+			global_version: Some(4),
+			transaction_items: vec![KeyValue {
+				key: "k1".to_string(),
+				version: 2,
+				value: b"k1v3".to_vec(),
+			}],
+			delete_items: vec![KeyValue { key: "k2".to_string(), version: 1, value: vec![] }],
+		};
+		let mock_server = mockito::mock("POST", PUT_OBJECT_ENDPOINT)
+			.match_body(expected_request.encode_to_vec())
+			.with_status(200)
+			.with_body(PutObjectResponse::default().encode_to_vec())
+			.create();
+
+		let client = client(mockito::server_url());
+		let response = VssTransaction::for_store("commit_store".to_string())
+			.put("k1".to_string(), 2, b"k1v3".to_vec())
+			.delete("k2".to_string(), 1)
+			.global_version(4)
+			.commit(&client)
+			.await
+			.unwrap();
+		assert_eq!(response, PutObjectResponse::default());
+		mock_server.expect(1).assert();
+	}
+
+	#[tokio::test]
+	async fn test_commit_conflict() {
+		let error_response = ErrorResponse {
+			error_code: ErrorCode::ConflictException.into(),
+			message: "ConflictException".to_string(),
+		};
+		let mock_server = mockito::mock("POST", PUT_OBJECT_ENDPOINT)
+			.match_body(Matcher::Any)
+			.with_status(409)
+			.with_body(error_response.encode_to_vec())
+			.create();
+
+		// The whole transaction is rejected if any of its conditions doesn't hold.
+		let client = client(mockito::server_url());
+		let result = VssTransaction::for_store("conflict_store".to_string())
+			.put("k1".to_string(), 0, b"k1v1".to_vec())
+			.delete("k2".to_string(), 3)
+			.commit(&client)
+			.await;
+		assert!(matches!(result, Err(VssError::ConflictError { .. })));
+		mock_server.expect(1).assert();
+	}
+
+	#[tokio::test]
+	async fn test_invalid_transaction_is_not_sent() {
+		let mock_server =
+			mockito::mock("POST", PUT_OBJECT_ENDPOINT).match_body(Matcher::Any).create();
+
+		let client = client(mockito::server_url());
+		let result = VssTransaction::for_store("invalid_store".to_string())
+			.put("k1".to_string(), 0, b"k1v1".to_vec())
+			.delete("k1".to_string(), 0)
+			.commit(&client)
+			.await;
+		assert!(matches!(result, Err(VssError::InvalidRequestError(..))));
+		mock_server.expect(0).assert();
+	}
+}